@@ -1,22 +1,48 @@
-use crate::uci::AsyncEngine;
-use crate::types::{TournamentConfig, TournamentMode, GameUpdate, EngineStats, ScheduledGame, TournamentError, TournamentResumeState};
-use crate::stats::TournamentStats;
-use shakmaty::{Chess, Position, Move, Role, Color, uci::Uci, CastlingMode, Outcome};
+use crate::uci::{AsyncEngine, HandshakeLine, DEFAULT_OUTPUT_BUFFER_LINES};
+use crate::types::{AdjudicationConfig, CompletionReason, EngineHealthConfig, EngineLogLine, EngineSearchStats, PairingSprtConcluded, Standings, TimeControl, TimeControlType, TournamentConfig, TournamentMode, GameUpdate, GameCompleted, EngineStats, ScheduledGame, TimeUpdate, TournamentComplete, TournamentError, TournamentResumeState, TournamentWarning, UciOption};
+use crate::stats::{StandingsTracker, TournamentResults, TournamentStats};
+use crate::diagnostics::{GameDiagnostics, MoveDiagnostics, TerminationReason};
+use crate::health::HealthMonitor;
+use crate::sprt::{GameResult, Sprt, SprtState};
+use shakmaty::{Chess, Position, Move, Role, Color, uci::Uci, san::SanPlus, CastlingMode, Outcome};
 use shakmaty::fen::Fen;
-use tokio::sync::{mpsc, Semaphore, broadcast};
+use shakmaty::zobrist::{Zobrist128, ZobristHash};
+use tokio::sync::{mpsc, Notify, Semaphore, broadcast};
 use tokio::time::{Instant, Duration, sleep, timeout};
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use rand::seq::SliceRandom;
-use rand::prelude::IndexedRandom;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::io::BufRead;
 use std::collections::{HashMap, HashSet, VecDeque};
 use tokio::task::JoinSet;
-use std::path::Path;
-
-const ENGINE_SPAWN_FAILURE_LIMIT: u32 = 3;
+use std::path::{Path, PathBuf};
+use tracing::{error, info, warn, Instrument};
+
+const DEFAULT_SPAWN_FAILURE_LIMIT: u32 = 3;
+const CHESS960_REGEN_ATTEMPTS: u32 = 3;
+const CHESS960_POSITIONS: u32 = 960;
+const MOVETIME_TIMEOUT_FACTOR: u64 = 5;
+/// Latency slack on a movetime search's timeout, the same 5s a clocked move gets.
+const MOVETIME_TIMEOUT_GRACE_MS: u64 = 5000;
+const ENGINE_EXIT_POLL_MS: u64 = 100;
+const CLOCK_TICK_MS: u64 = 200;
+const MOVE_BUDGET_SHARE: i64 = 20;
+const OVERSHOOT_FACTOR: i64 = 2;
+/// Overshoots needed, on at least half an engine's moves, before the PGN notes them.
+const MIN_OVERSHOT_MOVES: u32 = 3;
+/// Plies after which a game counts as half played in progress estimates.
+const PROGRESS_HALF_PLIES: f64 = 80.0;
+/// Fewest games queued per pairing at a time when playing until the SPRT resolves.
+const UNTIL_SPRT_BATCH: u32 = 2;
+/// Engine output lines kept per move to explain a stall.
+const STALL_LOG_LINES: usize = 10;
+/// PV plies sent with each move for board arrows unless `pv_plies` says otherwise.
+const DEFAULT_PV_PLIES: u32 = 4;
+const STANDARD_START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
 enum Board {
     Standard(Chess),
@@ -27,7 +53,19 @@ impl Board {
     fn turn(&self) -> Color { match self { Self::Standard(b) | Self::Chess960(b) => b.turn() } }
     fn is_game_over(&self) -> bool { match self { Self::Standard(b) | Self::Chess960(b) => b.is_game_over() } }
     fn outcome(&self) -> Option<Outcome> { match self { Self::Standard(b) | Self::Chess960(b) => b.outcome() } }
+    fn is_stalemate(&self) -> bool { match self { Self::Standard(b) | Self::Chess960(b) => b.is_stalemate() } }
+    fn halfmoves(&self) -> u32 { match self { Self::Standard(b) | Self::Chess960(b) => b.halfmoves() } }
     fn play_unchecked(&mut self, m: &Move) { match self { Self::Standard(b) | Self::Chess960(b) => b.play_unchecked(m) } }
+    /// Identity for repetition counting: pieces, side to move, castling rights and
+    /// an en passant square only when a capture there is actually legal.
+    fn repetition_key(&self) -> u128 {
+        match self { Self::Standard(b) | Self::Chess960(b) => b.zobrist_hash::<Zobrist128>(shakmaty::EnPassantMode::Legal).0 }
+    }
+    fn uci_move(&self, uci: &str) -> Option<Move> {
+        let uci = uci.parse::<Uci>().ok()?;
+        match self { Self::Standard(b) | Self::Chess960(b) => uci.to_move(b).ok() }
+    }
+    fn is_legal_uci(&self, uci: &str) -> bool { self.uci_move(uci).is_some() }
     fn to_fen_string(&self) -> String {
         match self {
             Self::Standard(b) => Fen::from_position(b.clone(), shakmaty::EnPassantMode::Legal).to_string(),
@@ -36,26 +74,136 @@ impl Board {
     }
 }
 
+/// The senders for every event the arbiter reports while it runs.
+pub struct ArbiterChannels {
+    pub game_update_tx: mpsc::Sender<GameUpdate>,
+    pub stats_tx: mpsc::Sender<EngineStats>,
+    pub tourney_stats_tx: mpsc::Sender<TournamentStats>,
+    pub schedule_update_tx: mpsc::Sender<ScheduledGame>,
+    pub error_tx: mpsc::Sender<TournamentError>,
+    pub warning_tx: mpsc::Sender<TournamentWarning>,
+    pub game_completed_tx: mpsc::Sender<GameCompleted>,
+    pub pairing_sprt_tx: mpsc::Sender<PairingSprtConcluded>,
+    pub time_update_tx: mpsc::Sender<TimeUpdate>,
+    pub engine_log_tx: mpsc::Sender<EngineLogLine>,
+}
+
+/// The receiving ends of [`ArbiterChannels`]. Dropping one is fine, the arbiter
+/// never stops on an event nobody listens to.
+pub struct ArbiterReceivers {
+    pub game_update_rx: mpsc::Receiver<GameUpdate>,
+    pub stats_rx: mpsc::Receiver<EngineStats>,
+    pub tourney_stats_rx: mpsc::Receiver<TournamentStats>,
+    pub schedule_update_rx: mpsc::Receiver<ScheduledGame>,
+    pub error_rx: mpsc::Receiver<TournamentError>,
+    pub warning_rx: mpsc::Receiver<TournamentWarning>,
+    pub game_completed_rx: mpsc::Receiver<GameCompleted>,
+    pub pairing_sprt_rx: mpsc::Receiver<PairingSprtConcluded>,
+    pub time_update_rx: mpsc::Receiver<TimeUpdate>,
+    pub engine_log_rx: mpsc::Receiver<EngineLogLine>,
+}
+
+impl ArbiterChannels {
+    /// Every channel, each buffering up to `capacity` events.
+    pub fn new(capacity: usize) -> (Self, ArbiterReceivers) {
+        let (game_update_tx, game_update_rx) = mpsc::channel(capacity);
+        let (stats_tx, stats_rx) = mpsc::channel(capacity);
+        let (tourney_stats_tx, tourney_stats_rx) = mpsc::channel(capacity);
+        let (schedule_update_tx, schedule_update_rx) = mpsc::channel(capacity);
+        let (error_tx, error_rx) = mpsc::channel(capacity);
+        let (warning_tx, warning_rx) = mpsc::channel(capacity);
+        let (game_completed_tx, game_completed_rx) = mpsc::channel(capacity);
+        let (pairing_sprt_tx, pairing_sprt_rx) = mpsc::channel(capacity);
+        let (time_update_tx, time_update_rx) = mpsc::channel(capacity);
+        let (engine_log_tx, engine_log_rx) = mpsc::channel(capacity);
+        (
+            Self { game_update_tx, stats_tx, tourney_stats_tx, schedule_update_tx, error_tx, warning_tx, game_completed_tx, pairing_sprt_tx, time_update_tx, engine_log_tx },
+            ArbiterReceivers { game_update_rx, stats_rx, tourney_stats_rx, schedule_update_rx, error_rx, warning_rx, game_completed_rx, pairing_sprt_rx, time_update_rx, engine_log_rx },
+        )
+    }
+}
+
+/// Lock order, for the locks ever held together: `tourney_stats`, then `standings`,
+/// then `should_stop`; `run_over` goes before `schedule_state` and `is_paused`.
+/// `schedule` is only ever held on its own, never across another lock or a channel
+/// send, so the scheduler and the UI commands can't deadlock on it.
 pub struct Arbiter {
     active_engines: Arc<Mutex<Vec<AsyncEngine>>>,
     config: TournamentConfig,
     game_update_tx: mpsc::Sender<GameUpdate>,
     stats_tx: mpsc::Sender<EngineStats>,
     tourney_stats_tx: mpsc::Sender<TournamentStats>,
-    pgn_tx: mpsc::Sender<String>,
+    pgn_tx: mpsc::Sender<PgnWriterCommand>,
+    live_pgn_tx: Option<mpsc::Sender<LivePgnUpdate>>,
     schedule_update_tx: mpsc::Sender<ScheduledGame>, // Channel for schedule updates
     error_tx: mpsc::Sender<TournamentError>,
+    warning_tx: mpsc::Sender<TournamentWarning>,
+    game_completed_tx: mpsc::Sender<GameCompleted>,
+    time_update_tx: mpsc::Sender<TimeUpdate>,
+    engine_log_tx: mpsc::Sender<EngineLogLine>,
     should_stop: Arc<Mutex<bool>>,
     is_paused: Arc<Mutex<bool>>,
+    pause_clock: Mutex<PauseClock>, // keeps paused time out of `max_duration_secs`
+    run_over: Mutex<bool>, // set once the schedule is played out; the resume file is final by then
     openings: Vec<String>,
+    opening_pins: Arc<HashMap<usize, String>>,
+    color_staggers: Vec<bool>, // per engine, see `gauntlet_staggers`
     tourney_stats: Arc<Mutex<TournamentStats>>,
-    schedule_queue: Arc<Mutex<VecDeque<ScheduleItem>>>,
-    pairing_states: Arc<Mutex<Vec<PairingState>>>,
+    standings: Arc<Mutex<StandingsTracker>>, // kept in step with `schedule_state` results
+    schedule: Arc<Mutex<ScheduleManager>>,
+    schedule_notify: Arc<Notify>, // wakes the scheduler when the queue changes or on stop
     remaining_rounds: Arc<Mutex<u32>>,
-    next_game_id: Arc<Mutex<usize>>,
     disabled_engine_ids: Arc<Mutex<HashSet<String>>>,
     schedule_state: Arc<Mutex<Vec<ScheduledGame>>>,
     engine_spawn_failures: Arc<Mutex<HashMap<String, u32>>>,
+    spawn_semaphore: Arc<Semaphore>,
+    active_games: Arc<Mutex<HashMap<usize, ActiveGame>>>,
+    pairing_sprt: Option<PairingSprtTracker>,
+}
+
+/// Handles to a running game so it can be aborted on its own.
+#[derive(Clone)]
+struct ActiveGame {
+    abort_flag: Arc<Mutex<bool>>,
+    engines: Vec<AsyncEngine>,
+}
+
+/// Time spent paused so far, so the wall-clock limit only counts time spent playing.
+#[derive(Default)]
+struct PauseClock {
+    paused_since: Option<Instant>,
+    paused_total: Duration,
+}
+
+impl PauseClock {
+    fn set_paused(&mut self, paused: bool) {
+        match (paused, self.paused_since) {
+            (true, None) => self.paused_since = Some(Instant::now()),
+            (false, Some(since)) => {
+                self.paused_total += since.elapsed();
+                self.paused_since = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn paused_for(&self) -> Duration {
+        self.paused_total + self.paused_since.map_or(Duration::ZERO, |since| since.elapsed())
+    }
+}
+
+/// Messages for the PGN writer task. Path switches travel on the same channel as
+/// games so every game lands in the file that was current when it finished.
+enum PgnWriterCommand {
+    Append(String),
+    SwitchPath { path: String, copy_existing: bool },
+}
+
+/// Messages for the live PGN writer. Each game's file is rewritten with its
+/// in-progress movetext on every move and deleted once the game is over.
+enum LivePgnUpdate {
+    Moves { game_id: usize, pgn: String },
+    Finished { game_id: usize },
 }
 
 #[derive(Clone)]
@@ -63,22 +211,37 @@ struct ScheduleItem {
     id: usize,
     idx_a: usize,
     idx_b: usize,
+    cycle: u32,
     game_idx: u32,
+    opening_index: usize,
+    white_idx: usize, // colors are settled once, when the game is scheduled
+    black_idx: usize,
     white_name: String,
     black_name: String,
 }
 
+/// The queue and the counters that decide what it holds next, behind one lock so
+/// a top-up, a removal and the scheduler taking a game never see each other half done.
+struct ScheduleManager {
+    queue: VecDeque<ScheduleItem>,
+    pairing_states: Vec<PairingState>,
+    next_game_id: usize, // last id handed out
+}
+
 #[derive(Clone)]
 struct PairingState {
     idx_a: usize,
     idx_b: usize,
+    cycle: u32,
     next_game_idx: u32,
+    concluded: bool, // pairing SPRT finished or the pairing was cancelled; no more games get scheduled
     disabled_engine_ids: Arc<Mutex<HashSet<String>>>,
     schedule_state: Arc<Mutex<Vec<ScheduledGame>>>,
 }
 
 impl Arbiter {
-    fn generate_pairings(config: &TournamentConfig) -> Vec<(usize, usize)> {
+    /// (idx_a, idx_b, cycle) for every pairing, the base set repeated once per cycle.
+    fn generate_pairings(config: &TournamentConfig) -> Vec<(usize, usize, u32)> {
         let n = config.engines.len();
         let mut pairings = Vec::new();
         match config.mode {
@@ -87,7 +250,12 @@ impl Arbiter {
             },
             TournamentMode::Gauntlet => {
                 if n >= 2 {
-                    for i in 1..n { pairings.push((0, i)); }
+                    let seeds = gauntlet_seeds(config);
+                    for &seed in &seeds {
+                        for i in (0..n).filter(|i| !seeds.contains(i)) {
+                            pairings.push((seed, i));
+                        }
+                    }
                 }
             },
             TournamentMode::RoundRobin => {
@@ -98,34 +266,54 @@ impl Arbiter {
                 }
             }
         }
-        pairings
+        let cycles = config.cycles.unwrap_or(1).max(1);
+        (0..cycles)
+            .flat_map(|cycle| pairings.iter().map(move |&(idx_a, idx_b)| (idx_a, idx_b, cycle)))
+            .collect()
     }
 
-    pub async fn new(
-        config: TournamentConfig,
-        game_update_tx: mpsc::Sender<GameUpdate>,
-        stats_tx: mpsc::Sender<EngineStats>,
-        tourney_stats_tx: mpsc::Sender<TournamentStats>,
-        schedule_update_tx: mpsc::Sender<ScheduledGame>, // Added
-        error_tx: mpsc::Sender<TournamentError>
-    ) -> anyhow::Result<Self> {
-        let mut openings = Vec::new();
-        if let Some(ref path) = config.opening.file {
-            openings = load_openings(path)?;
+    pub async fn new(mut config: TournamentConfig, channels: ArbiterChannels) -> anyhow::Result<Self> {
+        let ArbiterChannels { game_update_tx, stats_tx, tourney_stats_tx, schedule_update_tx, error_tx, warning_tx, game_completed_tx, pairing_sprt_tx, time_update_tx, engine_log_tx } = channels;
+        Self::validate_games_count(&config)?;
+        Self::validate_engine_identities(&config)?;
+        validate_pgn_tags(&config)?;
+        validate_tiebreak_order(&config)?;
+        validate_gauntlet_seeds(&config)?;
+        validate_go_styles(&config)?;
+        validate_time_control(&config)?;
+        // Fixed up front so the results file and resume state can replay this exact run.
+        config.opening.seed.get_or_insert_with(rand::random);
+        let (openings, opening_pins, skipped_openings) = load_opening_book(&config)?;
+
+        let (pgn_tx, mut pgn_rx) = mpsc::channel::<PgnWriterCommand>(100);
+
+        let benched = match_mode_benched(&config);
+        if !benched.is_empty() {
+            let message = format!("Match mode only plays {} vs {}; {} will not play", config.engines[0].name, config.engines[1].name, benched.join(", "));
+            warn!("{}", message);
+            let _ = warning_tx.send(TournamentWarning { game_id: None, message }).await;
         }
-
-        if let Some(order) = &config.opening.order {
-            if order == "random" {
-                let mut rng = rand::rng();
-                openings.shuffle(&mut rng);
-            }
+        if let Some(message) = unbalanced_colors_warning(&config) {
+            warn!("{}", message);
+            let _ = warning_tx.send(TournamentWarning { game_id: None, message }).await;
+        }
+        if skipped_openings > 0 {
+            let message = format!("Skipped {} invalid opening(s) in {}", skipped_openings, config.opening.file.as_deref().unwrap_or_default());
+            warn!("{}", message);
+            let _ = warning_tx.send(TournamentWarning { game_id: None, message }).await;
         }
 
-        let (pgn_tx, mut pgn_rx) = mpsc::channel::<String>(100);
+        // A per-game directory without a `pgn_path` writes per-game files only
+        let master_pgn = config.pgn_path.is_some() || config.pgn_per_game_dir.is_none();
+        let mut pgn_path = config.pgn_path.clone().unwrap_or_else(|| "tournament.pgn".to_string());
 
-        let pgn_path = config.pgn_path.clone().unwrap_or_else(|| "tournament.pgn".to_string());
+        // Appending to an earlier run's PGN: continue its round numbers. Resumed runs
+        // already wrote to this file, so they keep the offset stored in their config.
+        if master_pgn && config.round_offset.is_none() && !config.overwrite_pgn && !config.resume_from_state {
+            config.round_offset = Some(last_pgn_round(&pgn_path));
+        }
 
-        if config.overwrite_pgn {
+        if master_pgn && config.overwrite_pgn {
             if let Err(e) = tokio::fs::write(&pgn_path, "").await {
                  let _ = error_tx.send(TournamentError {
                         engine_id: None,
@@ -139,11 +327,16 @@ impl Arbiter {
         }
 
         let pgn_error_tx = error_tx.clone();
+        let pgn_sync = config.pgn_sync;
 
         tokio::spawn(async move {
-            let mut file = match OpenOptions::new().create(true).append(true).open(&pgn_path).await {
-                Ok(handle) => Some(handle),
-                Err(err) => {
+            // Without a master file games are dropped here until `set_pgn_path` names one
+            let mut master_pgn = master_pgn;
+            let opened = if master_pgn { Some(OpenOptions::new().create(true).append(true).open(&pgn_path).await) } else { None };
+            let mut file = match opened {
+                None => None,
+                Some(Ok(handle)) => Some(handle),
+                Some(Err(err)) => {
                     let _ = pgn_error_tx.send(TournamentError {
                         engine_id: None,
                         engine_name: "PGN Writer".to_string(),
@@ -157,7 +350,31 @@ impl Arbiter {
                 }
             };
 
-            while let Some(pgn) = pgn_rx.recv().await {
+            while let Some(command) = pgn_rx.recv().await {
+                let pgn = match command {
+                    PgnWriterCommand::Append(_) if !master_pgn => continue,
+                    PgnWriterCommand::Append(pgn) => pgn,
+                    PgnWriterCommand::SwitchPath { path, copy_existing } => {
+                        if copy_existing && master_pgn {
+                            if let Err(err) = append_file_contents(&pgn_path, &path).await {
+                                let _ = pgn_error_tx.send(TournamentError {
+                                    engine_id: None,
+                                    engine_name: "PGN Writer".to_string(),
+                                    game_id: None,
+                                    message: format!("Failed to copy PGN {} to {}: {}", pgn_path, path, err),
+                                    failure_count: 0,
+                                    disabled: false,
+                                }).await;
+                                warn!("Failed to copy PGN {} to {}: {}", pgn_path, path, err);
+                            }
+                        }
+                        pgn_path = path;
+                        master_pgn = true;
+                        file = None;
+                        continue;
+                    }
+                };
+
                 if file.is_none() {
                     match OpenOptions::new().create(true).append(true).open(&pgn_path).await {
                         Ok(handle) => file = Some(handle),
@@ -209,6 +426,16 @@ impl Arbiter {
                                     disabled: false,
                                 }).await;
                                 eprintln!("Failed to flush PGN file {} after retry: {}", pgn_path, retry_err);
+                            } else if let Err(retry_err) = sync_pgn(&retry_handle, pgn_sync).await {
+                                let _ = pgn_error_tx.send(TournamentError {
+                                    engine_id: None,
+                                    engine_name: "PGN Writer".to_string(),
+                                    game_id: None,
+                                    message: format!("Failed to sync PGN file {} after retry: {}", pgn_path, retry_err),
+                                    failure_count: 0,
+                                    disabled: false,
+                                }).await;
+                                warn!("Failed to sync PGN file {} after retry: {}", pgn_path, retry_err);
                             } else {
                                 file = Some(retry_handle);
                             }
@@ -227,27 +454,92 @@ impl Arbiter {
                         }).await;
                         eprintln!("Failed to flush PGN file {}: {}", pgn_path, err);
                         file = None;
+                    } else if let Err(err) = sync_pgn(handle, pgn_sync).await {
+                        let _ = pgn_error_tx.send(TournamentError {
+                            engine_id: None,
+                            engine_name: "PGN Writer".to_string(),
+                            game_id: None,
+                            message: format!("Failed to sync PGN file {}: {}", pgn_path, err),
+                            failure_count: 0,
+                            disabled: false,
+                        }).await;
+                        warn!("Failed to sync PGN file {}: {}", pgn_path, err);
                     }
                 }
             }
         });
 
+        let live_pgn_tx = config.live_pgn_path.clone().map(|live_path| {
+            let (live_tx, mut live_rx) = mpsc::channel::<LivePgnUpdate>(100);
+            let live_error_tx = error_tx.clone();
+            tokio::spawn(async move {
+                while let Some(update) = live_rx.recv().await {
+                    let (game_id, written) = match update {
+                        LivePgnUpdate::Moves { game_id, pgn } => {
+                            let path = live_pgn_file_path(&live_path, game_id);
+                            (game_id, tokio::fs::write(&path, pgn.as_bytes()).await.map_err(|err| (path, err)))
+                        }
+                        // Games that never got a move have no file to remove
+                        LivePgnUpdate::Finished { game_id } => {
+                            let path = live_pgn_file_path(&live_path, game_id);
+                            match tokio::fs::remove_file(&path).await {
+                                Err(err) if err.kind() != std::io::ErrorKind::NotFound => (game_id, Err((path, err))),
+                                _ => (game_id, Ok(())),
+                            }
+                        }
+                    };
+                    if let Err((path, err)) = written {
+                        let _ = live_error_tx.send(TournamentError {
+                            engine_id: None,
+                            engine_name: "PGN Writer".to_string(),
+                            game_id: Some(game_id),
+                            message: format!("Failed to update live PGN {}: {}", path.display(), err),
+                            failure_count: 0,
+                            disabled: false,
+                        }).await;
+                        warn!("Failed to update live PGN {}: {}", path.display(), err);
+                    }
+                }
+            });
+            live_tx
+        });
+
         let pairings = Self::generate_pairings(&config);
-        let remaining_rounds = config.games_count.max(1);
+        let remaining_rounds = if plays_until_sprt(&config) {
+            until_sprt_batch(&config)
+        } else {
+            config.games_count.max(1)
+        };
         let disabled_engine_ids_set: HashSet<String> = config.disabled_engine_ids.iter().cloned().collect();
         let disabled_engine_ids = Arc::new(Mutex::new(disabled_engine_ids_set));
         let schedule_state = Arc::new(Mutex::new(Vec::new()));
 
-        let pairing_states = pairings.iter().map(|(idx_a, idx_b)| PairingState {
-            idx_a: *idx_a,
-            idx_b: *idx_b,
+        let pairing_states = pairings.iter().map(|&(idx_a, idx_b, cycle)| PairingState {
+            idx_a,
+            idx_b,
+            cycle,
             next_game_idx: 0,
+            concluded: false,
             disabled_engine_ids: disabled_engine_ids.clone(),
             schedule_state: schedule_state.clone(),
         }).collect();
 
         let sprt_enabled = config.sprt_enabled;
         let sprt_config = config.sprt_config.clone();
+        let standings = StandingsTracker::new(&config.engines);
+        // Spawn permits cover launch + handshake only; games keep playing after release.
+        let max_spawns = config.max_concurrent_spawns
+            .map(|limit| limit.max(1) as usize)
+            .unwrap_or(Semaphore::MAX_PERMITS);
+        let schedule = Arc::new(Mutex::new(ScheduleManager { queue: VecDeque::new(), pairing_states, next_game_id: 0 }));
+        let pairing_sprt = config.pairing_sprt_enabled.then(|| PairingSprtTracker {
+            sprt_config: config.sprt_config.clone().unwrap_or_default(),
+            pairs: Arc::new(Mutex::new(HashMap::new())),
+            schedule: schedule.clone(),
+            schedule_update_tx: schedule_update_tx.clone(),
+            concluded_tx: pairing_sprt_tx,
+        });
+        let color_staggers = gauntlet_staggers(&config);
 
         Ok(Self {
             active_engines: Arc::new(Mutex::new(Vec::new())),
@@ -256,38 +548,74 @@ impl Arbiter {
             stats_tx,
             tourney_stats_tx,
             pgn_tx,
+            live_pgn_tx,
             schedule_update_tx,
             error_tx,
+            warning_tx,
+            game_completed_tx,
+            time_update_tx,
+            engine_log_tx,
             should_stop: Arc::new(Mutex::new(false)),
             is_paused: Arc::new(Mutex::new(false)),
+            pause_clock: Mutex::new(PauseClock::default()),
+            run_over: Mutex::new(false),
             openings,
+            opening_pins: Arc::new(opening_pins),
+            color_staggers,
             tourney_stats: Arc::new(Mutex::new(TournamentStats::new(sprt_enabled, sprt_config))),
-            schedule_queue: Arc::new(Mutex::new(VecDeque::new())),
-            pairing_states: Arc::new(Mutex::new(pairing_states)),
+            standings: Arc::new(Mutex::new(standings)),
+            schedule,
+            schedule_notify: Arc::new(Notify::new()),
             remaining_rounds: Arc::new(Mutex::new(remaining_rounds)),
-            next_game_id: Arc::new(Mutex::new(0)),
             disabled_engine_ids,
             schedule_state,
             engine_spawn_failures: Arc::new(Mutex::new(HashMap::new())),
+            spawn_semaphore: Arc::new(Semaphore::new(max_spawns)),
+            active_games: Arc::new(Mutex::new(HashMap::new())),
+            pairing_sprt,
         })
     }
 
-    pub async fn set_paused(&self, paused: bool) { *self.is_paused.lock().await = paused; }
+    /// Also saved right away, so a tournament closed while paused resumes paused.
+    /// Once the run is over there is nothing to resume and nothing is saved.
+    pub async fn set_paused(&self, paused: bool) {
+        *self.is_paused.lock().await = paused;
+        self.pause_clock.lock().await.set_paused(paused);
+        // Held while saving, so the save can't land after the finished run removed the file
+        let run_over = self.run_over.lock().await;
+        if *run_over {
+            return;
+        }
+        if let Err(err) = self.persist_tournament_state().await {
+            warn!("Failed to persist schedule state: {}", err);
+        }
+    }
+
+    /// Whether `max_duration_secs` of unpaused time has passed since `started`.
+    async fn time_limit_reached(&self, started: Instant) -> bool {
+        let Some(secs) = self.config.max_duration_secs else { return false };
+        let paused = self.pause_clock.lock().await.paused_for();
+        started.elapsed().saturating_sub(paused) >= Duration::from_secs(secs)
+    }
 
-    fn make_schedule_item(&self, idx_a: usize, idx_b: usize, game_idx: u32, game_id: usize) -> ScheduleItem {
-        let (white_idx, black_idx) = if self.config.swap_sides && game_idx % 2 != 0 {
+    fn make_schedule_item(config: &TournamentConfig, staggers: &[bool], idx_a: usize, idx_b: usize, cycle: u32, game_idx: u32, game_id: usize) -> ScheduleItem {
+        let (white_idx, black_idx) = if colors_swapped(config, staggers[idx_b], cycle, game_idx) {
             (idx_b, idx_a)
         } else {
             (idx_a, idx_b)
         };
-        let white_name = self.config.engines[white_idx].name.clone();
-        let black_name = self.config.engines[black_idx].name.clone();
+        let white_name = config.engines[white_idx].name.clone();
+        let black_name = config.engines[black_idx].name.clone();
 
         ScheduleItem {
             id: game_id,
             idx_a,
             idx_b,
+            cycle,
             game_idx,
+            opening_index: opening_index(config, game_idx),
+            white_idx,
+            black_idx,
             white_name,
             black_name,
         }
@@ -300,7 +628,96 @@ impl Arbiter {
             black_name: item.black_name.clone(),
             state: state.to_string(),
             result,
+            start_fen: None,
+            opening_index: Some(item.opening_index),
+            opening: None,
+        }
+    }
+
+    /// `games_count` 0 means "play until the SPRT resolves", so it needs SPRT;
+    /// there is no other way to ask for an open-ended run.
+    pub fn validate_games_count(config: &TournamentConfig) -> anyhow::Result<()> {
+        if plays_until_sprt(config) && !config.sprt_enabled {
+            return Err(anyhow::anyhow!("games_count 0 (play until the SPRT resolves) requires SPRT to be enabled"));
+        }
+        Ok(())
+    }
+
+    /// Chess960 needs every engine to declare `UCI_Chess960`; one that doesn't
+    /// ignores the `setoption` and gets castling wrong mid-game. Runs each engine's
+    /// `uci` handshake, so it's checked when a match starts rather than in `new`.
+    pub async fn validate_engine_capabilities(config: &TournamentConfig) -> anyhow::Result<()> {
+        if config.variant != "chess960" {
+            return Ok(());
+        }
+        // Launched like a game would, from the engine's own working directory
+        let mut checked = HashSet::new();
+        for (idx, engine) in config.engines.iter().enumerate() {
+            if !checked.insert((engine.path.as_str(), engine.working_directory.as_deref())) {
+                continue;
+            }
+            let process = spawn_engine_in(engine, engine.working_directory.as_deref().map(Path::new), false).await
+                .map_err(|e| anyhow::anyhow!("Could not query {}: {}", engine.name, e))?;
+            let handshake = uci_handshake(&process, engine, idx).await;
+            let _ = process.quit().await;
+            let (options, _) = handshake.map_err(|e| anyhow::anyhow!("Could not query {}: {}", engine.name, e))?;
+            if !options.iter().any(|opt| opt.name.eq_ignore_ascii_case("UCI_Chess960")) {
+                return Err(anyhow::anyhow!("{} does not declare UCI_Chess960, so it can't play Chess960", engine.name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Standings, the schedule and the crosstable key engines by name, and failure
+    /// counts and disabling by id, so both must be unique within a tournament.
+    pub fn validate_engine_identities(config: &TournamentConfig) -> anyhow::Result<()> {
+        let mut names = HashSet::new();
+        let mut ids = HashSet::new();
+        for engine in &config.engines {
+            if !names.insert(engine.name.as_str()) {
+                return Err(anyhow::anyhow!("Engine name \"{}\" is used more than once; names must be unique", engine.name));
+            }
+            if let Some(id) = engine.id.as_deref() {
+                if !ids.insert(id) {
+                    return Err(anyhow::anyhow!("Engine id \"{}\" is used more than once; ids must be unique", id));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The full schedule a fresh `run_tournament` would play for `config`, in
+    /// order, without spawning any engine. Games whose start position is only
+    /// generated at game time (Chess960 drawn per game) have no `start_fen`; with
+    /// `opening.order = "random"` and no `opening.seed` the real run reshuffles the book.
+    pub fn preview_schedule(config: &TournamentConfig) -> anyhow::Result<Vec<ScheduledGame>> {
+        Self::validate_games_count(config)?;
+        Self::validate_engine_identities(config)?;
+        validate_gauntlet_seeds(config)?;
+        let (openings, opening_pins, _) = load_opening_book(config)?;
+        let pairings = Self::generate_pairings(config);
+        let staggers = gauntlet_staggers(config);
+        let rounds = if plays_until_sprt(config) { until_sprt_batch(config) } else { config.games_count.max(1) };
+        let repetitions = config.repetitions.unwrap_or(1).max(1);
+
+        let mut next_game_idx = vec![0u32; pairings.len()];
+        let mut next_game_id = 0;
+        let mut schedule = Vec::new();
+        for _ in 0..repetitions {
+            for (&(idx_a, idx_b, cycle), game_idx) in pairings.iter().zip(next_game_idx.iter_mut()) {
+                for _ in 0..rounds {
+                    next_game_id += 1;
+                    let item = Self::make_schedule_item(config, &staggers, idx_a, idx_b, cycle, *game_idx, next_game_id);
+                    *game_idx += 1;
+                    let mut scheduled = Self::schedule_item_to_game(&item, "Pending", None);
+                    scheduled.start_fen = assigned_start_fen(config, &openings, &opening_pins, &item)
+                        .or_else(|| (config.variant != "chess960").then(|| generate_start_fen(&config.variant, &mut game_rng(config, item.id))));
+                    scheduled.opening = scheduled.start_fen.as_deref().map(opening_label);
+                    schedule.push(scheduled);
+                }
+            }
         }
+        Ok(schedule)
     }
 
     pub async fn update_remaining_rounds(&self, remaining_rounds: u32) -> anyhow::Result<()> {
@@ -309,18 +726,17 @@ impl Arbiter {
         let mut pending_updates = Vec::new();
         let mut removed_updates = Vec::new();
 
-        let mut queue = self.schedule_queue.lock().await;
-        let mut pairing_states = self.pairing_states.lock().await;
-        let mut next_game_id = self.next_game_id.lock().await;
+        let mut schedule = self.schedule.lock().await;
+        let ScheduleManager { queue, pairing_states, next_game_id } = &mut *schedule;
 
-        let mut pending_counts: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut pending_counts: HashMap<(usize, usize, u32), usize> = HashMap::new();
         for item in queue.iter() {
-            *pending_counts.entry((item.idx_a, item.idx_b)).or_insert(0) += 1;
+            *pending_counts.entry((item.idx_a, item.idx_b, item.cycle)).or_insert(0) += 1;
         }
 
-        let mut remove_needed: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut remove_needed: HashMap<(usize, usize, u32), usize> = HashMap::new();
         for state in pairing_states.iter() {
-            let key = (state.idx_a, state.idx_b);
+            let key = (state.idx_a, state.idx_b, state.cycle);
             let current = *pending_counts.get(&key).unwrap_or(&0);
             if current > remaining_rounds as usize {
                 remove_needed.insert(key, current - remaining_rounds as usize);
@@ -330,16 +746,25 @@ impl Arbiter {
         if !remove_needed.is_empty() {
             let queue_vec: Vec<ScheduleItem> = queue.drain(..).collect();
             let mut remove_ids = HashSet::new();
+            let mut rewind_to: HashMap<(usize, usize, u32), u32> = HashMap::new();
             for item in queue_vec.iter().rev() {
-                let key = (item.idx_a, item.idx_b);
+                let key = (item.idx_a, item.idx_b, item.cycle);
                 if let Some(needed) = remove_needed.get_mut(&key) {
                     if *needed > 0 {
                         *needed -= 1;
                         remove_ids.insert(item.id);
+                        rewind_to.insert(key, item.game_idx);
                         removed_updates.push(Self::schedule_item_to_game(item, "Removed", None));
                     }
                 }
             }
+            // Removed games are the newest of their pairing, so handing their slots
+            // back keeps game indices (and with them colors and book lines) gapless.
+            for state in pairing_states.iter_mut() {
+                if let Some(&game_idx) = rewind_to.get(&(state.idx_a, state.idx_b, state.cycle)) {
+                    state.next_game_idx = game_idx;
+                }
+            }
             let retained: VecDeque<ScheduleItem> = queue_vec.into_iter()
                 .filter(|item| !remove_ids.contains(&item.id))
                 .collect();
@@ -348,11 +773,11 @@ impl Arbiter {
 
         pending_counts.clear();
         for item in queue.iter() {
-            *pending_counts.entry((item.idx_a, item.idx_b)).or_insert(0) += 1;
+            *pending_counts.entry((item.idx_a, item.idx_b, item.cycle)).or_insert(0) += 1;
         }
 
-        for state in pairing_states.iter_mut() {
-            let key = (state.idx_a, state.idx_b);
+        for state in pairing_states.iter_mut().filter(|state| !state.concluded) {
+            let key = (state.idx_a, state.idx_b, state.cycle);
             let current = *pending_counts.get(&key).unwrap_or(&0);
             if current < remaining_rounds as usize {
                 let add_count = remaining_rounds as usize - current;
@@ -361,15 +786,27 @@ impl Arbiter {
                     let game_id = *next_game_id;
                     let game_idx = state.next_game_idx;
                     state.next_game_idx += 1;
-                    let item = self.make_schedule_item(state.idx_a, state.idx_b, game_idx, game_id);
-                    pending_updates.push(Self::schedule_item_to_game(&item, "Pending", None));
+                    let item = Self::make_schedule_item(&self.config, &self.color_staggers, state.idx_a, state.idx_b, state.cycle, game_idx, game_id);
+                    let mut scheduled = Self::schedule_item_to_game(&item, "Pending", None);
+                    scheduled.opening = assigned_start_fen(&self.config, &self.openings, &self.opening_pins, &item).as_deref().map(opening_label);
+                    pending_updates.push(scheduled);
                     queue.push_back(item);
                 }
             }
         }
 
-        drop(pairing_states);
-        drop(queue);
+        drop(schedule);
+        self.schedule_notify.notify_one();
+
+        // Games an earlier run saved as Pending would otherwise come back on resume
+        {
+            let mut saved = self.schedule_state.lock().await;
+            for update in &removed_updates {
+                if let Some(game) = saved.iter_mut().find(|game| game.id == update.id) {
+                    game.state = update.state.clone();
+                }
+            }
+        }
 
         for update in removed_updates {
             let _ = self.schedule_update_tx.send(update).await;
@@ -381,17 +818,118 @@ impl Arbiter {
         Ok(())
     }
 
+    /// Moves every queued game between the two engines, in either color, to the front
+    /// of the queue in their existing order. Games already running have left the queue
+    /// and are untouched. Returns how many games moved.
+    pub async fn prioritize_pairing(&self, idx_a: usize, idx_b: usize) -> anyhow::Result<usize> {
+        let engine_count = self.config.engines.len();
+        if idx_a >= engine_count || idx_b >= engine_count || idx_a == idx_b {
+            return Err(anyhow::anyhow!("No pairing between engines {} and {}", idx_a, idx_b));
+        }
+
+        let mut schedule = self.schedule.lock().await;
+        let (mut prioritized, rest): (VecDeque<ScheduleItem>, VecDeque<ScheduleItem>) = schedule.queue.drain(..)
+            .partition(|item| (item.idx_a, item.idx_b) == (idx_a, idx_b) || (item.idx_a, item.idx_b) == (idx_b, idx_a));
+        let moved = prioritized.len();
+        prioritized.extend(rest);
+        schedule.queue = prioritized;
+        drop(schedule);
+        self.schedule_notify.notify_one();
+        Ok(moved)
+    }
+
+    /// Drops every queued game between the two engines, in either color and every
+    /// cycle, and keeps `update_remaining_rounds` from scheduling the pairing again.
+    /// Games already running finish normally. Returns how many games were removed.
+    pub async fn cancel_pairing(&self, idx_a: usize, idx_b: usize) -> anyhow::Result<usize> {
+        let engine_count = self.config.engines.len();
+        if idx_a >= engine_count || idx_b >= engine_count || idx_a == idx_b {
+            return Err(anyhow::anyhow!("No pairing between engines {} and {}", idx_a, idx_b));
+        }
+        let is_pairing = |a: usize, b: usize| (a, b) == (idx_a, idx_b) || (a, b) == (idx_b, idx_a);
+
+        let removed: Vec<ScheduleItem> = {
+            let mut schedule = self.schedule.lock().await;
+            schedule.pairing_states.iter_mut()
+                .filter(|state| is_pairing(state.idx_a, state.idx_b))
+                .for_each(|state| state.concluded = true);
+            let (removed, kept): (Vec<ScheduleItem>, Vec<ScheduleItem>) = schedule.queue.drain(..)
+                .partition(|item| is_pairing(item.idx_a, item.idx_b));
+            schedule.queue = kept.into();
+            removed
+        };
+        self.schedule_notify.notify_one();
+        for item in &removed {
+            let _ = self.schedule_update_tx.send(Self::schedule_item_to_game(item, "Removed", None)).await;
+        }
+        Ok(removed.len())
+    }
+
+    /// Replaces the disabled set. Engines taken out of it start over with no
+    /// failures, so a fixed engine isn't disabled again by its old count.
     pub async fn set_disabled_engine_ids(&self, disabled_engine_ids: Vec<String>) {
-        let mut disabled_ids = self.disabled_engine_ids.lock().await;
-        *disabled_ids = disabled_engine_ids.into_iter().collect();
+        let disabled_engine_ids: HashSet<String> = disabled_engine_ids.into_iter().collect();
+        let reenabled: Vec<String> = {
+            let mut disabled_ids = self.disabled_engine_ids.lock().await;
+            let reenabled = disabled_ids.difference(&disabled_engine_ids).cloned().collect();
+            *disabled_ids = disabled_engine_ids;
+            reenabled
+        };
+        let mut failures = self.engine_spawn_failures.lock().await;
+        for id in &reenabled {
+            failures.remove(id);
+        }
+    }
+
+    /// Lets a disabled engine play its remaining games again, with its failure
+    /// count cleared. Games it already forfeited stay forfeited.
+    pub async fn reenable_engine(&self, engine_id: &str) {
+        self.disabled_engine_ids.lock().await.remove(engine_id);
+        self.engine_spawn_failures.lock().await.remove(engine_id);
     }
 
+    /// Restores a saved schedule and replays the games it already decided into the
+    /// tournament stats, so a resumed run reports totals, Elo and SPRT for the whole event.
     pub async fn load_schedule_state(&self, schedule: Vec<ScheduledGame>) {
+        let pairings = Self::generate_pairings(&self.config);
+        let mut by_id: Vec<&ScheduledGame> = schedule.iter().collect();
+        by_id.sort_by_key(|game| game.id);
+        let mut stats = self.tourney_stats.lock().await;
+        for game in by_id {
+            // Each repetition gets its own head-to-head and SPRT, as when it was played
+            while stats.repetition < game_repetition(&pairings, self.config.games_count, game.id) {
+                stats.finish_repetition();
+                stats.start_repetition();
+            }
+            if !matches!(game.state.as_str(), "Finished" | "Skipped") { continue; }
+            let Some(result) = game.result.as_deref() else { continue };
+            let Some((idx_a, ..)) = compute_game_mapping(&pairings, self.config.games_count, game.id) else { continue };
+            // Forfeits were counted with their plain result
+            stats.update(result.trim_end_matches(" (forfeit)"), game.white_name == self.config.engines[idx_a].name);
+        }
+        let standings = StandingsTracker::from_schedule(&schedule, &self.config.engines);
+        stats.update_standings(standings.standings(self.config.tiebreak_order.as_deref()));
+        stats.update_head_to_head(standings.head_to_head());
+        *self.standings.lock().await = standings;
+        let _ = self.tourney_stats_tx.send(stats.clone()).await;
+        drop(stats);
         *self.schedule_state.lock().await = schedule;
     }
 
     async fn persist_tournament_state(&self) -> anyhow::Result<()> {
-        persist_resume_state(&self.config.resume_state_path, &self.schedule_state, &self.config).await
+        persist_resume_state(&self.config.resume_state_path, &self.schedule_state, &self.is_paused, &self.config).await
+    }
+
+    pub async fn live_standings(&self) -> Standings {
+        Standings { entries: self.standings.lock().await.standings(self.config.tiebreak_order.as_deref()) }
+    }
+
+    /// The start FEN each played game used, keyed by game id, for `opening.pins_file`.
+    pub async fn opening_pins(&self) -> HashMap<usize, String> {
+        let schedule = self.schedule_state.lock().await;
+        schedule.iter()
+            .filter_map(|game| game.start_fen.clone().map(|fen| (game.id, fen)))
+            .collect()
     }
 
     pub fn remove_resume_state_file(path: &str) -> anyhow::Result<()> {
@@ -401,74 +939,159 @@ impl Arbiter {
         Ok(())
     }
 
-    pub async fn run_tournament(&self) -> anyhow::Result<()> {
-        let concurrency = self.config.concurrency.unwrap_or(4).max(1) as usize;
+    /// One warning per pairing whose engines would share on-disk hash or log files.
+    async fn warn_shared_engine_files(&self, pairings: &[(usize, usize, u32)]) {
+        let mut checked = HashSet::new();
+        for &(idx_a, idx_b, _) in pairings {
+            let (a, b) = (&self.config.engines[idx_a], &self.config.engines[idx_b]);
+            if !checked.insert((idx_a, idx_b)) || !engines_share_files(a, b) {
+                continue;
+            }
+            let message = if self.config.isolate_engine_dirs {
+                format!("{} and {} run the same executable from the same directory; each process gets its own temporary working directory", a.name, b.name)
+            } else {
+                format!("{} and {} run the same executable from the same directory; on-disk hash or log files may collide", a.name, b.name)
+            };
+            let _ = self.warning_tx.send(TournamentWarning { game_id: None, message }).await;
+        }
+    }
+
+    /// Advisory when the games running at once want more threads than there are logical cores.
+    async fn warn_oversubscribed(&self, concurrency: u32) {
+        let Some(cores) = available_cores() else { return; };
+        let threads = max_engine_threads(&self.config);
+        if concurrency * threads <= cores {
+            return;
+        }
+        let _ = self.warning_tx.send(TournamentWarning {
+            game_id: None,
+            message: format!("{} concurrent games at up to {} engine threads each need {} cores, but only {} are available; results may suffer", concurrency, threads, concurrency * threads, cores),
+        }).await;
+    }
+
+    /// Plays the schedule out and reports how the tournament ended.
+    pub async fn run_tournament(&self) -> anyhow::Result<TournamentComplete> {
+        let started = Instant::now();
+        let timed_out = self.play_tournament(started).await?;
+        let reason = if self.config.sprt_enabled && sprt_resolved(&*self.tourney_stats.lock().await) {
+            CompletionReason::SprtResolved
+        } else if *self.should_stop.lock().await {
+            CompletionReason::Stopped
+        } else if timed_out {
+            CompletionReason::TimeLimitReached
+        } else {
+            CompletionReason::AllGamesDone
+        };
+        Ok(self.completion_summary(reason, started.elapsed()).await)
+    }
+
+    pub async fn completion_summary(&self, reason: CompletionReason, elapsed: Duration) -> TournamentComplete {
+        let games_played = self.schedule_state.lock().await.iter().filter(|game| game.state == "Finished").count() as u32;
+        TournamentComplete {
+            stats: self.tourney_stats.lock().await.clone(),
+            games_played,
+            elapsed_ms: elapsed.as_millis() as u64,
+            reason,
+        }
+    }
+
+    /// Returns whether `max_duration_secs` ran out before the schedule did.
+    async fn play_tournament(&self, started: Instant) -> anyhow::Result<bool> {
+        let concurrency = effective_concurrency(&self.config) as usize;
+        self.warn_oversubscribed(concurrency as u32).await;
         let semaphore = Arc::new(Semaphore::new(concurrency));
         let pairings = Self::generate_pairings(&self.config);
         let games_count = self.config.games_count;
+        let repetitions = self.config.repetitions.unwrap_or(1).max(1);
+        let mut repetition = 1;
 
         {
-            let mut queue = self.schedule_queue.lock().await;
-            queue.clear();
-        }
-        {
-            let mut pairing_states = self.pairing_states.lock().await;
-            for state in pairing_states.iter_mut() {
+            let mut schedule = self.schedule.lock().await;
+            schedule.queue.clear();
+            schedule.next_game_id = 0;
+            for state in schedule.pairing_states.iter_mut() {
                 state.next_game_idx = 0;
             }
         }
         if self.config.resume_from_state {
-            let schedule = self.schedule_state.lock().await.clone();
+            let saved_schedule = self.schedule_state.lock().await.clone();
 
-            for scheduled_game in &schedule {
+            for scheduled_game in &saved_schedule {
                 let _ = self.schedule_update_tx.send(scheduled_game.clone()).await;
             }
 
-            let mut queue = self.schedule_queue.lock().await;
-            let mut pairing_states = self.pairing_states.lock().await;
+            let mut schedule = self.schedule.lock().await;
             let mut max_id = 0;
-            let mut max_game_indices: HashMap<(usize, usize), u32> = HashMap::new();
+            let mut max_game_indices: HashMap<(usize, usize, u32), u32> = HashMap::new();
 
-            for scheduled_game in &schedule {
+            for scheduled_game in &saved_schedule {
                 max_id = max_id.max(scheduled_game.id);
-                if let Some((idx_a, idx_b, game_idx)) = compute_game_mapping(&pairings, games_count, scheduled_game.id) {
-                     let entry = max_game_indices.entry((idx_a, idx_b)).or_insert(0);
+                if let Some((idx_a, idx_b, cycle, game_idx)) = compute_game_mapping(&pairings, games_count, scheduled_game.id) {
+                     let entry = max_game_indices.entry((idx_a, idx_b, cycle)).or_insert(0);
                      *entry = (*entry).max(game_idx + 1);
 
                      if scheduled_game.state == "Pending" || scheduled_game.state == "Active" {
-                          let item = self.make_schedule_item(idx_a, idx_b, game_idx, scheduled_game.id);
-                          queue.push_back(item);
+                          let mut item = Self::make_schedule_item(&self.config, &self.color_staggers, idx_a, idx_b, cycle, game_idx, scheduled_game.id);
+                          if let Some(opening_index) = scheduled_game.opening_index {
+                              item.opening_index = opening_index;
+                          }
+                          schedule.queue.push_back(item);
                      }
                 }
             }
 
-            for state in pairing_states.iter_mut() {
-                if let Some(next_idx) = max_game_indices.get(&(state.idx_a, state.idx_b)) {
+            for state in schedule.pairing_states.iter_mut() {
+                if let Some(next_idx) = max_game_indices.get(&(state.idx_a, state.idx_b, state.cycle)) {
                     state.next_game_idx = *next_idx;
                 }
             }
 
-            let mut next_game_id = self.next_game_id.lock().await;
-            *next_game_id = max_id;
+            schedule.next_game_id = max_id;
+            repetition = game_repetition(&pairings, games_count, max_id);
         } else {
-             {
-                 let mut next_game_id = self.next_game_id.lock().await;
-                 *next_game_id = 0;
-             }
              let remaining_rounds = *self.remaining_rounds.lock().await;
              self.update_remaining_rounds(remaining_rounds).await?;
         }
 
         let mut join_set = JoinSet::new();
         self.persist_tournament_state().await?;
+        self.warn_shared_engine_files(&pairings).await;
 
+        let mut timed_out = false;
+        // Staggered starts only cover the first climb to `concurrency`
+        let spawn_stagger = self.config.spawn_stagger_ms.filter(|ms| *ms > 0).map(Duration::from_millis);
+        let mut ramped_up = false;
         loop {
             if *self.should_stop.lock().await {
                 break;
             }
 
+            if !timed_out && self.time_limit_reached(started).await {
+                // Games in flight still finish and count
+                timed_out = true;
+                info!("Time limit of {} s reached; no new games are started", self.config.max_duration_secs.unwrap_or(0));
+                self.update_remaining_rounds(0).await?;
+                self.persist_tournament_state().await?;
+            }
+
+            if plays_until_sprt(&self.config) && !timed_out {
+                let resolved = sprt_resolved(&*self.tourney_stats.lock().await);
+                let queue_empty = self.schedule.lock().await.queue.is_empty();
+                if resolved && !queue_empty {
+                    // Drop what hasn't started; games in flight still finish and count
+                    self.update_remaining_rounds(0).await?;
+                } else if !resolved && queue_empty {
+                    self.update_remaining_rounds(until_sprt_batch(&self.config)).await?;
+                }
+            }
+
             while join_set.len() < concurrency {
-                let next_game = { self.schedule_queue.lock().await.pop_front() };
+                // Wait before taking the game, so a removal or reprioritisation during the pause still applies
+                if let Some(stagger) = spawn_stagger.filter(|_| !ramped_up && !join_set.is_empty()) {
+                    if self.schedule.lock().await.queue.is_empty() { break; }
+                    sleep(stagger).await;
+                }
+                let next_game = { self.schedule.lock().await.queue.pop_front() };
                 let Some(game) = next_game else { break };
                 let permit = semaphore.clone().acquire_owned().await?;
 
@@ -481,23 +1104,29 @@ impl Arbiter {
                 let tourney_stats_tx = self.tourney_stats_tx.clone();
                 let tourney_stats = self.tourney_stats.clone();
                 let pgn_tx = self.pgn_tx.clone();
+                let live_pgn_tx = self.live_pgn_tx.clone();
                 let schedule_update_tx = self.schedule_update_tx.clone();
                 let openings = self.openings.clone();
+                let opening_pins = self.opening_pins.clone();
                 let error_tx = self.error_tx.clone();
+                let warning_tx = self.warning_tx.clone();
+                let game_completed_tx = self.game_completed_tx.clone();
+                let time_update_tx = self.time_update_tx.clone();
+                let engine_log_tx = self.engine_log_tx.clone();
                 let engine_spawn_failures = self.engine_spawn_failures.clone();
+                let spawn_semaphore = self.spawn_semaphore.clone();
+                let active_games = self.active_games.clone();
+                let pairing_sprt = self.pairing_sprt.clone();
                 let disabled_engine_ids = self.disabled_engine_ids.clone();
                 let schedule_state = self.schedule_state.clone();
+                let standings = self.standings.clone();
                 let resume_state_path = self.config.resume_state_path.clone();
 
                 join_set.spawn(async move {
                     let _permit = permit;
                     if *should_stop.lock().await { return; }
 
-                    let (white_engine_idx, black_engine_idx) = if config.swap_sides && game.game_idx % 2 != 0 {
-                        (game.idx_b, game.idx_a)
-                    } else {
-                        (game.idx_a, game.idx_b)
-                    };
+                    let (white_engine_idx, black_engine_idx) = (game.white_idx, game.black_idx);
 
                     let (white_disabled, black_disabled) = {
                         let disabled_ids = disabled_engine_ids.lock().await;
@@ -507,6 +1136,9 @@ impl Arbiter {
                         )
                     };
 
+                    // Generated start positions are only known once the game starts
+                    let opening = assigned_start_fen(&config, &openings, &opening_pins, &game).as_deref().map(opening_label);
+
                     if white_disabled || black_disabled {
                         let (display_result, base_result) = forfeit_result(white_disabled, black_disabled);
                         let skipped_update = ScheduledGame {
@@ -515,18 +1147,28 @@ impl Arbiter {
                             black_name: config.engines[black_engine_idx].name.clone(),
                             state: "Skipped".to_string(),
                             result: Some(display_result),
+                            start_fen: None,
+                            opening_index: Some(game.opening_index),
+                            opening: opening.clone(),
                         };
                         update_schedule_state(&schedule_state, skipped_update.clone()).await;
+                        if let Some(result) = skipped_update.result.as_deref() {
+                            standings.lock().await.record(&skipped_update.white_name, &skipped_update.black_name, result);
+                        }
                         let _ = schedule_update_tx.send(skipped_update).await;
 
                         if let Some(base_result) = base_result {
                              let mut stats = tourney_stats.lock().await;
-                             let is_white_a = white_engine_idx == 0;
+                             let is_white_a = white_engine_idx == game.idx_a;
                              stats.update(&base_result, is_white_a);
                              if should_stop_for_sprt(&config, &stats) {
                                  *should_stop.lock().await = true;
                              }
                              let _ = tourney_stats_tx.send(stats.clone()).await;
+                             drop(stats);
+                             if let Some(tracker) = pairing_sprt.as_ref() {
+                                 tracker.record(&config, game.idx_a, game.idx_b, &base_result, is_white_a).await;
+                             }
                         }
                         return;
                     }
@@ -537,7 +1179,10 @@ impl Arbiter {
                         white_name: game.white_name.clone(),
                         black_name: game.black_name.clone(),
                         state: "Active".to_string(),
-                        result: None
+                        result: None,
+                        start_fen: None,
+                        opening_index: Some(game.opening_index),
+                        opening,
                     };
                     update_schedule_state(&schedule_state, active_update.clone()).await;
                     let _ = schedule_update_tx.send(active_update).await;
@@ -547,76 +1192,37 @@ impl Arbiter {
 
                     let eng_a_key = eng_a_config.id.clone().unwrap_or_else(|| eng_a_config.name.clone());
                     let eng_b_key = eng_b_config.id.clone().unwrap_or_else(|| eng_b_config.name.clone());
+                    let game_span = tracing::info_span!("game", game_id = game.id, white = %game.white_name, black = %game.black_name, pairing = %format!("{}-{}", game.idx_a.min(game.idx_b), game.idx_a.max(game.idx_b)));
+
+                    let Ok(spawn_permit) = spawn_semaphore.acquire_owned().await else { return; };
+                    let failure_context = FailureContext {
+                        engine_spawn_failures: &engine_spawn_failures,
+                        disabled_engine_ids: &disabled_engine_ids,
+                        error_tx: &error_tx,
+                        tourney_stats: &tourney_stats,
+                        stop_on_disable: config.abort_on_engine_disable.then_some(&should_stop),
+                        failure_limit: spawn_failure_limit(&config),
+                    };
 
-                    let engine_a = match AsyncEngine::spawn(&eng_a_config.path).await {
-                        Ok(e) => {
-                            let mut failures = engine_spawn_failures.lock().await;
-                            failures.remove(&eng_a_key);
-                            e
-                        }
+                    let isolated = config.isolate_engine_dirs && engines_share_files(eng_a_config, eng_b_config);
+                    let dir_a = engine_working_dir(&config, game.id, game.idx_a, game.idx_b);
+                    let dir_b = engine_working_dir(&config, game.id, game.idx_b, game.idx_a);
+                    let engine_a = match spawn_engine_in(eng_a_config, dir_a.as_deref(), isolated).instrument(game_span.clone()).await {
+                        Ok(e) => e,
                         Err(e) => {
-                            let failure_count = {
-                                let mut failures = engine_spawn_failures.lock().await;
-                                let entry = failures.entry(eng_a_key.clone()).or_insert(0);
-                                *entry += 1;
-                                *entry
-                            };
-                            let disabled = if failure_count >= ENGINE_SPAWN_FAILURE_LIMIT {
-                                if let Some(id) = eng_a_config.id.as_ref() {
-                                    let mut disabled_ids = disabled_engine_ids.lock().await;
-                                    disabled_ids.insert(id.clone());
-                                    true
-                                } else {
-                                    false
-                                }
-                            } else {
-                                false
-                            };
-                            let _ = error_tx.send(TournamentError {
-                                engine_id: eng_a_config.id.clone(),
-                                engine_name: eng_a_config.name.clone(),
-                                game_id: Some(game.id),
-                                message: format!("Failed to spawn engine {}: {}", eng_a_config.name, e),
-                                failure_count,
-                                disabled,
-                            }).await;
-                            println!("Failed to spawn engine {}: {}", eng_a_config.name, e);
+                            let message = format!("Failed to spawn engine {}: {}", eng_a_config.name, e);
+                            report_engine_failure(&failure_context, eng_a_config, game.id, message).await;
+                            remove_isolated_dirs(isolated, [dir_a, dir_b]).await;
                             return;
                         }
                     };
-                    let engine_b = match AsyncEngine::spawn(&eng_b_config.path).await {
-                        Ok(e) => {
-                            let mut failures = engine_spawn_failures.lock().await;
-                            failures.remove(&eng_b_key);
-                            e
-                        }
+                    let engine_b = match spawn_engine_in(eng_b_config, dir_b.as_deref(), isolated).instrument(game_span.clone()).await {
+                        Ok(e) => e,
                         Err(e) => {
-                            let failure_count = {
-                                let mut failures = engine_spawn_failures.lock().await;
-                                let entry = failures.entry(eng_b_key.clone()).or_insert(0);
-                                *entry += 1;
-                                *entry
-                            };
-                            let disabled = if failure_count >= ENGINE_SPAWN_FAILURE_LIMIT {
-                                if let Some(id) = eng_b_config.id.as_ref() {
-                                    let mut disabled_ids = disabled_engine_ids.lock().await;
-                                    disabled_ids.insert(id.clone());
-                                    true
-                                } else {
-                                    false
-                                }
-                            } else {
-                                false
-                            };
-                            let _ = error_tx.send(TournamentError {
-                                engine_id: eng_b_config.id.clone(),
-                                engine_name: eng_b_config.name.clone(),
-                                game_id: Some(game.id),
-                                message: format!("Failed to spawn engine {}: {}", eng_b_config.name, e),
-                                failure_count,
-                                disabled,
-                            }).await;
-                            println!("Failed to spawn engine {}: {}", eng_b_config.name, e);
+                            let message = format!("Failed to spawn engine {}: {}", eng_b_config.name, e);
+                            report_engine_failure(&failure_context, eng_b_config, game.id, message).await;
+                            let _ = engine_a.kill().await;
+                            remove_isolated_dirs(isolated, [dir_a, dir_b]).await;
                             return;
                         }
                     };
@@ -626,161 +1232,356 @@ impl Arbiter {
                         active.push(engine_a.clone());
                         active.push(engine_b.clone());
                     }
-
-                    let mut a_rx = engine_a.stdout_broadcast.subscribe();
-                    let mut b_rx = engine_b.stdout_broadcast.subscribe();
-                    let stats_tx_a = stats_tx.clone();
-                    let stats_tx_b = stats_tx.clone();
-                    let idx_a_val = game.idx_a;
-                    let idx_b_val = game.idx_b;
-
-                    let stop_listen_a = should_stop.clone();
-                    tokio::spawn(async move {
-                        loop {
-                            match a_rx.recv().await {
-                                Ok(line) => {
-                                    if *stop_listen_a.lock().await { break; }
-                                    if line.starts_with("info") { if let Some(stats) = parse_info_with_id(&line, idx_a_val, game.id) { let _ = stats_tx_a.send(stats).await; } }
-                                },
-                                Err(broadcast::error::RecvError::Lagged(_)) => continue,
-                                Err(broadcast::error::RecvError::Closed) => break,
-                            }
-                        }
+                    let abort_flag = Arc::new(Mutex::new(false));
+                    active_games.lock().await.insert(game.id, ActiveGame {
+                        abort_flag: abort_flag.clone(),
+                        engines: vec![engine_a.clone(), engine_b.clone()],
                     });
 
-                    let stop_listen_b = should_stop.clone();
-                    tokio::spawn(async move {
-                        loop {
-                            match b_rx.recv().await {
-                                Ok(line) => {
-                                    if *stop_listen_b.lock().await { break; }
-                                    if line.starts_with("info") { if let Some(stats) = parse_info_with_id(&line, idx_b_val, game.id) { let _ = stats_tx_b.send(stats).await; } }
-                                },
-                                Err(broadcast::error::RecvError::Lagged(_)) => continue,
-                                Err(broadcast::error::RecvError::Closed) => break,
-                            }
-                        }
-                    });
+                    let engine_log_dir = config.engine_log_dir.as_deref().map(Path::new);
+                    spawn_info_listener(&engine_a, eng_a_config, game.idx_a, game.id, config.engine_health, engine_log_dir, &stats_tx, &error_tx, &engine_log_tx, &should_stop, &game_span);
+                    spawn_info_listener(&engine_b, eng_b_config, game.idx_b, game.id, config.engine_health, engine_log_dir, &stats_tx, &error_tx, &engine_log_tx, &should_stop, &game_span);
 
-                let (white_engine, black_engine, white_idx, black_idx) = if config.swap_sides && game.game_idx % 2 != 0 {
-                    (&engine_b, &engine_a, game.idx_b, game.idx_a)
-                } else {
-                    (&engine_a, &engine_b, game.idx_a, game.idx_b)
-                };
+                let (white_idx, black_idx) = (game.white_idx, game.black_idx);
+                let (white_engine, black_engine) = if white_idx == game.idx_a { (&engine_a, &engine_b) } else { (&engine_b, &engine_a) };
 
                 let white_name_pgn = config.engines[white_idx].name.clone();
                 let black_name_pgn = config.engines[black_idx].name.clone();
 
-                let start_fen = if !openings.is_empty() {
-                    let idx = if config.swap_sides { (game.game_idx / 2) as usize } else { game.game_idx as usize };
-                    openings[idx % openings.len()].clone()
-                } else if let Some(ref f) = config.opening.fen {
-                    if !f.trim().is_empty() { f.clone() } else { generate_start_fen(&config.variant) }
+                let assigned_fen = assigned_start_fen(&config, &openings, &opening_pins, &game);
+                let mut opening_source = if opening_pins.contains_key(&game.id) {
+                    "pin".to_string()
+                } else if config.opening.start_fens.is_some() {
+                    "start fens".to_string()
+                } else if !openings.is_empty() {
+                    "book".to_string()
+                } else if configured_fen(&config).is_some() {
+                    "configured fen".to_string()
+                } else if let Some(sp) = chess960_sp(&config, &game) {
+                    format!("chess960 sp {}", sp)
                 } else {
-                    generate_start_fen(&config.variant)
+                    "generated".to_string()
                 };
+                let regenerable_960 = config.variant == "chess960" && assigned_fen.is_none();
+                let mut position_rng = game_rng(&config, game.id);
+                let mut start_fen = assigned_fen.unwrap_or_else(|| generate_start_fen(&config.variant, &mut position_rng));
+                if !regenerable_960 && !is_valid_start_fen(&start_fen, &config.variant) {
+                    let _ = warning_tx.send(TournamentWarning {
+                        game_id: Some(game.id),
+                        message: format!("Invalid opening FEN {}, using the standard start position", start_fen),
+                    }).await;
+                    warn!(parent: &game_span, "Game {}: invalid opening FEN {}, using the standard start position", game.id, start_fen);
+                    start_fen = STANDARD_START_FEN.to_string();
+                    opening_source.push_str(", invalid, replaced by the standard start position");
+                }
 
-                let res = play_game_static(
-                    white_engine, black_engine, white_idx, black_idx, &start_fen,
-        &config, &game_update_tx, &should_stop, &is_paused, game.id
-                ).await;
+                // Only a finished handshake clears an engine's failure count, so one that
+                // launches but never answers `uci` still runs into the limit
+                let nodestime = config.time_control.nodestime.map(|n| n.max(1));
+                let opponent = |idx: usize| config.send_uci_opponent.then(|| config.engines[idx].name.as_str());
+                let mut handshake = Ok(());
+                for (engine, idx, opponent_idx) in [(white_engine, white_idx, black_idx), (black_engine, black_idx, white_idx)] {
+                    handshake = initialize_engine(engine, &config.engines[idx], idx, &config.variant, nodestime, opponent(opponent_idx)).instrument(game_span.clone()).await;
+                    if handshake.is_err() {
+                        break;
+                    }
+                    engine_spawn_failures.lock().await.remove(if idx == game.idx_a { &eng_a_key } else { &eng_b_key });
+                }
+                drop(spawn_permit);
+
+                let mut regen_attempts = 0;
+                let res = match handshake {
+                    Err(err) => Err(err),
+                    Ok(()) => loop {
+                        let res = play_game_static(
+                            white_engine, black_engine, white_idx, black_idx, &start_fen,
+                &config, &game_update_tx, &time_update_tx, live_pgn_tx.as_ref(), &should_stop, &abort_flag, &is_paused, &active_engines, &active_games, game.id,
+                            regenerable_960 && start_fen != STANDARD_START_FEN
+                        ).instrument(game_span.clone()).await;
+                        let rejected = matches!(&res, Err(err) if err.downcast_ref::<StartPositionRejected>().is_some());
+                        if !rejected {
+                            break res;
+                        }
+                        regen_attempts += 1;
+                        let rejected_fen = std::mem::replace(&mut start_fen, if regen_attempts < CHESS960_REGEN_ATTEMPTS {
+                            generate_start_fen(&config.variant, &mut position_rng)
+                        } else {
+                            STANDARD_START_FEN.to_string()
+                        });
+                        let _ = warning_tx.send(TournamentWarning {
+                            game_id: Some(game.id),
+                            message: format!("Chess960 start position {} was rejected, retrying with {}", rejected_fen, start_fen),
+                        }).await;
+                        warn!(parent: &game_span, "Game {}: Chess960 start position {} rejected, retrying with {}", game.id, rejected_fen, start_fen);
+                        opening_source.push_str(&format!(", {} rejected", rejected_fen));
+                    },
+                };
+                // Finished games are in the tournament PGN; stopped ones start over on resume
+                if let Some(live_tx) = live_pgn_tx.as_ref() {
+                    let _ = live_tx.send(LivePgnUpdate::Finished { game_id: game.id }).await;
+                }
 
                 match res {
-                    Ok((result, moves_played)) => {
+                    Ok(PlayedGame { result, moves: moves_played, white_search, black_search, white_restarts, black_restarts, mut diagnostics }) => {
+                        if config.diagnostics {
+                            diagnostics.opening_source = opening_source;
+                            let path = GameDiagnostics::file_path(config.pgn_path.as_deref(), game.id);
+                            if let Err(err) = diagnostics.write(&path).await {
+                                let _ = error_tx.send(TournamentError {
+                                    engine_id: None,
+                                    engine_name: "Diagnostics".to_string(),
+                                    game_id: Some(game.id),
+                                    message: format!("Failed to write {}: {}", path.display(), err),
+                                    failure_count: 0,
+                                    disabled: false,
+                                }).await;
+                            }
+                        }
                         // Notify Finished
                         let finished_update = ScheduledGame {
                                 id: game.id,
                                 white_name: game.white_name.clone(),
                                 black_name: game.black_name.clone(),
                             state: "Finished".to_string(),
-                            result: Some(result.clone())
+                            result: Some(result.clone()),
+                            start_fen: Some(start_fen.clone()),
+                            opening_index: Some(game.opening_index),
+                            opening: Some(opening_label(&start_fen)),
                         };
                         update_schedule_state(&schedule_state, finished_update.clone()).await;
                         let _ = schedule_update_tx.send(finished_update).await;
-                        if let Err(err) = persist_resume_state(&resume_state_path, &schedule_state, &config).await {
-                            println!("Failed to persist schedule state: {}", err);
+                        let _ = game_completed_tx.send(GameCompleted {
+                            id: game.id,
+                            white: white_name_pgn.clone(),
+                            black: black_name_pgn.clone(),
+                            result: result.clone(),
+                            plies: moves_played.len() as u32,
+                        }).await;
+                        if let Err(err) = persist_resume_state(&resume_state_path, &schedule_state, &is_paused, &config).await {
+                            error!(parent: &game_span, "Game {}: failed to persist schedule state: {}", game.id, err);
                         }
 
-                        let event_name = config.event_name.as_deref().unwrap_or("CCRL GUI Tournament");
-                        let pgn = format_pgn(&moves_played, &result, &white_name_pgn, &black_name_pgn, &start_fen, event_name, game.id);
-                        let _ = pgn_tx.send(pgn).await;
+                        let mut search_summary = format!("White: {}; Black: {}", format_search_summary(&white_search), format_search_summary(&black_search));
+                        if let Some(ending) = diagnostics.termination.as_ref().and_then(|termination| ending_comment(termination.reason, &result)) {
+                            search_summary = format!("{}. {}", ending, search_summary);
+                        }
+                        let termination = termination_tag(&diagnostics);
+                        let pgn = format_pgn(&PgnGame {
+                            moves: &moves_played,
+                            result: &result,
+                            white: &white_name_pgn,
+                            black: &black_name_pgn,
+                            start_fen: split_opening(&start_fen).0,
+                            round: pgn_round(&config, game.id),
+                            termination: termination.as_deref(),
+                            final_comment: Some(&search_summary),
+                        }, &PgnHeader::from_config(&config));
+                        if let Some(dir) = config.pgn_per_game_dir.as_deref() {
+                            let path = per_game_pgn_path(dir, game.id);
+                            if let Err(err) = write_game_pgn(&path, &pgn).await {
+                                let _ = error_tx.send(TournamentError {
+                                    engine_id: None,
+                                    engine_name: "PGN Writer".to_string(),
+                                    game_id: Some(game.id),
+                                    message: format!("Failed to write {}: {}", path.display(), err),
+                                    failure_count: 0,
+                                    disabled: false,
+                                }).await;
+                                error!("Failed to write PGN {}: {}", path.display(), err);
+                            }
+                        }
+                        let _ = pgn_tx.send(PgnWriterCommand::Append(pgn)).await;
 
                         {
                             let mut stats = tourney_stats.lock().await;
-                            let is_white_a = white_idx == 0;
+                            let is_white_a = white_idx == game.idx_a;
                             stats.update(&result, is_white_a);
+                            stats.record_game_length(moves_played.len() as u32);
+                            stats.record_engine_search(&white_name_pgn, &white_search);
+                            stats.record_engine_search(&black_name_pgn, &black_search);
+                            for _ in 0..white_restarts { stats.record_crash(&white_name_pgn); }
+                            for _ in 0..black_restarts { stats.record_crash(&black_name_pgn); }
+                            let loser = match result.as_str() {
+                                "1-0" => Some(&black_name_pgn),
+                                "0-1" => Some(&white_name_pgn),
+                                _ => None,
+                            };
+                            match (diagnostics.termination.as_ref().map(|t| t.reason), loser) {
+                                (Some(TerminationReason::TimeForfeit), Some(loser)) => stats.record_timeout(loser),
+                                (Some(TerminationReason::EngineDisconnected), Some(loser)) => stats.record_crash(loser),
+                                _ => {}
+                            }
 
-                            // Re-calculate Standings from Schedule State
-                            // This is a bit heavy (O(N) where N is games), but safe for <10k games
-                            // Better than maintaining complex incremental state
-                            let schedule = schedule_state.lock().await.clone();
-                            let standings = crate::stats::calculate_standings(&schedule, &config.engines);
-                            stats.update_standings(standings);
+                            // Only this game's two engines change; SB and ranks are redone from the running totals
+                            {
+                                let mut standings = standings.lock().await;
+                                standings.record(&game.white_name, &game.black_name, &result);
+                                stats.update_standings(standings.standings(config.tiebreak_order.as_deref()));
+                                stats.update_head_to_head(standings.head_to_head());
+                            }
 
                             if should_stop_for_sprt(&config, &stats) {
                                 *should_stop.lock().await = true;
                             }
                             let _ = tourney_stats_tx.send(stats.clone()).await;
                         }
+                        if let Some(tracker) = pairing_sprt.as_ref() {
+                            tracker.record(&config, game.idx_a, game.idx_b, &result, white_idx == game.idx_a).await;
+                        }
                     }
                     Err(err) => {
                             if err.to_string() != "stopped" {
-                                println!("Game {} failed: {}", game.id, err);
+                                error!(parent: &game_span, "Game {} failed: {}", game.id, err);
+                            }
+                            if let Some(init_err) = err.downcast_ref::<EngineInitError>() {
+                                let engine_config = &config.engines[init_err.engine_idx];
+                                report_engine_failure(&failure_context, engine_config, game.id, init_err.message.clone()).await;
                             }
+                            // A game cut short by stopping is replayed on resume
+                            let state = if err.to_string() == "stopped" { "Pending" } else { "Aborted" };
                             let aborted_update = ScheduledGame {
                                 id: game.id,
                                 white_name: game.white_name.clone(),
                                 black_name: game.black_name.clone(),
-                                state: "Aborted".to_string(),
-                                result: None
+                                state: state.to_string(),
+                                result: None,
+                                start_fen: Some(start_fen.clone()),
+                                opening_index: Some(game.opening_index),
+                                opening: Some(opening_label(&start_fen)),
                             };
                             update_schedule_state(&schedule_state, aborted_update.clone()).await;
                             let _ = schedule_update_tx.send(aborted_update).await;
-                            if let Err(err) = persist_resume_state(&resume_state_path, &schedule_state, &config).await {
-                                println!("Failed to persist schedule state: {}", err);
+                            if let Err(err) = persist_resume_state(&resume_state_path, &schedule_state, &is_paused, &config).await {
+                                error!(parent: &game_span, "Game {}: failed to persist schedule state: {}", game.id, err);
                             }
                         }
                     }
 
-                    let _ = engine_a.quit().await;
-                    let _ = engine_b.quit().await;
+                    // Includes any engine restarted mid-game
+                    let game_engines = active_games.lock().await.remove(&game.id)
+                        .map(|active_game| active_game.engines)
+                        .unwrap_or_else(|| vec![engine_a.clone(), engine_b.clone()]);
+                    for engine in game_engines {
+                        let _ = engine.quit().await;
+                    }
+                    remove_isolated_dirs(isolated, [dir_a, dir_b]).await;
                 });
             }
+            ramped_up |= join_set.len() >= concurrency;
 
             if join_set.is_empty() {
-                let has_pending = { !self.schedule_queue.lock().await.is_empty() };
+                let has_pending = { !self.schedule.lock().await.queue.is_empty() };
                 if !has_pending {
+                    if repetition < repetitions && !timed_out {
+                        repetition += 1;
+                        {
+                            let mut stats = self.tourney_stats.lock().await;
+                            stats.finish_repetition();
+                            stats.start_repetition();
+                            let _ = self.tourney_stats_tx.send(stats.clone()).await;
+                        }
+                        // Game ids and per-pairing game indices keep counting up, so the
+                        // next run gets fresh round numbers and the next openings in the book.
+                        let remaining_rounds = *self.remaining_rounds.lock().await;
+                        self.update_remaining_rounds(remaining_rounds).await?;
+                        self.persist_tournament_state().await?;
+                        continue;
+                    }
                     break;
                 }
-                sleep(Duration::from_millis(100)).await;
                 continue;
             }
 
-            let _ = join_set.join_next().await;
+            // Wake on a finished game (free slot) or a queue change, whichever comes first
+            tokio::select! {
+                _ = join_set.join_next() => {}
+                _ = self.schedule_notify.notified() => {}
+            }
         }
 
         if *self.should_stop.lock().await {
             while join_set.join_next().await.is_some() {}
         }
 
+        if repetitions > 1 {
+            let mut stats = self.tourney_stats.lock().await;
+            if stats.total_games > 0 {
+                stats.finish_repetition();
+                let _ = self.tourney_stats_tx.send(stats.clone()).await;
+            }
+        }
+
         {
             let mut active = self.active_engines.lock().await;
             active.clear();
         }
 
+        if plays_until_sprt(&self.config) {
+            let stats = self.tourney_stats.lock().await.clone();
+            let _ = self.tourney_stats_tx.send(stats).await;
+        }
+
+        if let Some(path) = self.config.results_path.as_ref() {
+            if let Err(err) = self.write_results(path).await {
+                let _ = self.error_tx.send(TournamentError {
+                    engine_id: None,
+                    engine_name: "Results Writer".to_string(),
+                    game_id: None,
+                    message: format!("Failed to write results file {}: {}", path, err),
+                    failure_count: 0,
+                    disabled: false,
+                }).await;
+            }
+        }
+
+        let mut run_over = self.run_over.lock().await;
+        *run_over = true;
         if let Some(path) = self.config.resume_state_path.as_ref() {
             let schedule = self.schedule_state.lock().await;
-            let all_done = schedule.iter().all(|game| game.state == "Finished" || game.state == "Aborted");
+            let all_done = schedule.iter().all(|game| matches!(game.state.as_str(), "Finished" | "Aborted" | "Removed"));
             if all_done {
                 let _ = Self::remove_resume_state_file(path);
             }
         }
 
+        Ok(timed_out)
+    }
+
+    async fn write_results(&self, path: &str) -> anyhow::Result<()> {
+        let completed = !*self.should_stop.lock().await;
+        let schedule = self.schedule_state.lock().await.clone();
+        let stats = self.tourney_stats.lock().await.clone();
+        let results = TournamentResults::new(&schedule, &stats, &self.config, completed);
+        tokio::fs::write(path, serde_json::to_string_pretty(&results)?).await?;
+        Ok(())
+    }
+
+    /// Sends games finishing from now on to `path`. With `copy_existing`, games
+    /// already written are appended to the new file first.
+    pub async fn set_pgn_path(&self, path: String, copy_existing: bool) -> anyhow::Result<()> {
+        let path = path.trim().to_string();
+        if path.is_empty() {
+            return Err(anyhow::anyhow!("PGN path must not be empty"));
+        }
+        self.pgn_tx.send(PgnWriterCommand::SwitchPath { path, copy_existing }).await
+            .map_err(|_| anyhow::anyhow!("PGN writer is not running"))
+    }
+
+    /// Aborts one running game; the rest of the tournament carries on.
+    pub async fn abort_game(&self, game_id: usize) -> anyhow::Result<()> {
+        let active_game = self.active_games.lock().await.get(&game_id).cloned()
+            .ok_or_else(|| anyhow::anyhow!("Game {} is not in progress", game_id))?;
+        *active_game.abort_flag.lock().await = true;
+        // Killing the engines unblocks a game stuck waiting for bestmove
+        for engine in active_game.engines {
+            let _ = engine.kill().await;
+        }
         Ok(())
     }
 
     pub async fn stop(&self) {
         *self.should_stop.lock().await = true;
+        self.schedule_notify.notify_one();
 
         let engines_to_stop = {
             let mut active = self.active_engines.lock().await;
@@ -793,10 +1594,203 @@ impl Arbiter {
             let _ = engine.quit().await;
         }
     }
+
+    /// Stops like `stop` when the process is about to exit, and persists the resume
+    /// state right away with games in flight back to Pending, as `resume_match` would.
+    pub async fn shutdown(&self) -> anyhow::Result<()> {
+        self.stop().await;
+        {
+            let mut schedule = self.schedule_state.lock().await;
+            for game in schedule.iter_mut().filter(|game| game.state == "Active") {
+                game.state = "Pending".to_string();
+                game.result = None;
+            }
+        }
+        self.persist_tournament_state().await
+    }
 }
 
-fn is_engine_disabled(disabled_ids: &HashSet<String>, engine_id: Option<&str>) -> bool {
-    engine_id.map_or(false, |id| disabled_ids.contains(id))
+/// SPRT per pairing (from `idx_a`'s side). When one concludes, that pairing's
+/// queued games are removed and it is never topped up again.
+#[derive(Clone)]
+struct PairingSprtTracker {
+    sprt_config: crate::sprt::SprtConfig,
+    pairs: Arc<Mutex<HashMap<(usize, usize), Sprt>>>,
+    schedule: Arc<Mutex<ScheduleManager>>,
+    schedule_update_tx: mpsc::Sender<ScheduledGame>,
+    concluded_tx: mpsc::Sender<PairingSprtConcluded>,
+}
+
+impl PairingSprtTracker {
+    async fn record(&self, config: &TournamentConfig, idx_a: usize, idx_b: usize, result: &str, is_white_a: bool) {
+        let Some(game_result) = GameResult::from_result_str(result, is_white_a) else { return };
+        let status = {
+            let mut pairs = self.pairs.lock().await;
+            let sprt = pairs.entry((idx_a, idx_b)).or_insert_with(|| Sprt::new(self.sprt_config.clone()));
+            sprt.update_sprt(game_result)
+        };
+        if matches!(status.state, SprtState::Continue) {
+            return;
+        }
+
+        let removed: Vec<ScheduleItem> = {
+            let mut schedule = self.schedule.lock().await;
+            // Every cycle of the pairing stops together
+            let mut states = schedule.pairing_states.iter_mut().filter(|s| s.idx_a == idx_a && s.idx_b == idx_b).peekable();
+            if states.peek().is_none_or(|state| state.concluded) {
+                return;
+            }
+            states.for_each(|state| state.concluded = true);
+            let (removed, kept): (Vec<ScheduleItem>, Vec<ScheduleItem>) = schedule.queue.drain(..)
+                .partition(|item| item.idx_a == idx_a && item.idx_b == idx_b);
+            schedule.queue = kept.into();
+            removed
+        };
+        for item in &removed {
+            let _ = self.schedule_update_tx.send(Arbiter::schedule_item_to_game(item, "Removed", None)).await;
+        }
+
+        let _ = self.concluded_tx.send(PairingSprtConcluded {
+            engine_a: config.engines[idx_a].name.clone(),
+            engine_b: config.engines[idx_b].name.clone(),
+            state: status.state.to_string(),
+            llr: status.llr,
+            wins: status.wins,
+            draws: status.draws,
+            losses: status.losses,
+        }).await;
+    }
+}
+
+/// What `play_game_static` hands back for a game that reached a result.
+struct PlayedGame {
+    result: String,
+    moves: Vec<String>,
+    white_search: EngineSearchStats,
+    black_search: EngineSearchStats,
+    white_restarts: u32,
+    black_restarts: u32,
+    diagnostics: GameDiagnostics,
+}
+
+/// Handshake failure that counts toward the spawn failure limit like a failed spawn.
+#[derive(Debug)]
+struct EngineInitError {
+    engine_idx: usize,
+    message: String,
+}
+
+impl std::fmt::Display for EngineInitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for EngineInitError {}
+
+/// An engine broke the UCI protocol mid-game; forfeits without a restart.
+#[derive(Debug)]
+struct ProtocolViolation(String);
+
+impl std::fmt::Display for ProtocolViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ProtocolViolation {}
+
+/// The tournament was paused mid-search: the engine got `stop` after thinking
+/// for this long, and the move is asked again from the same position on resume.
+#[derive(Debug)]
+struct SearchPaused(Duration);
+
+impl std::fmt::Display for SearchPaused {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "search paused after {} ms", self.0.as_millis())
+    }
+}
+
+impl std::error::Error for SearchPaused {}
+
+/// A generated Chess960 start couldn't be played; the caller draws another one.
+#[derive(Debug)]
+struct StartPositionRejected;
+
+impl std::fmt::Display for StartPositionRejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "start position rejected")
+    }
+}
+
+impl std::error::Error for StartPositionRejected {}
+
+/// The shared state `report_engine_failure` counts, disables and reports through.
+struct FailureContext<'a> {
+    engine_spawn_failures: &'a Arc<Mutex<HashMap<String, u32>>>,
+    disabled_engine_ids: &'a Arc<Mutex<HashSet<String>>>,
+    error_tx: &'a mpsc::Sender<TournamentError>,
+    tourney_stats: &'a Arc<Mutex<TournamentStats>>,
+    stop_on_disable: Option<&'a Arc<Mutex<bool>>>, // set when `abort_on_engine_disable`
+    failure_limit: u32,
+}
+
+/// Bumps the engine's consecutive failure count, disables it once the limit is
+/// reached (engines without an id can't be disabled) and reports the error.
+async fn report_engine_failure(context: &FailureContext<'_>, engine_config: &crate::types::EngineConfig, game_id: usize, message: String) {
+    let FailureContext { engine_spawn_failures, disabled_engine_ids, error_tx, tourney_stats, stop_on_disable, failure_limit } = *context;
+    tourney_stats.lock().await.record_crash(&engine_config.name);
+    let key = engine_config.id.clone().unwrap_or_else(|| engine_config.name.clone());
+    let failure_count = {
+        let mut failures = engine_spawn_failures.lock().await;
+        let entry = failures.entry(key).or_insert(0);
+        *entry += 1;
+        *entry
+    };
+    let disabled = if failure_count >= failure_limit {
+        if let Some(id) = engine_config.id.as_ref() {
+            let mut disabled_ids = disabled_engine_ids.lock().await;
+            disabled_ids.insert(id.clone());
+            true
+        } else {
+            false
+        }
+    } else {
+        false
+    };
+    error!("{}", message);
+    let _ = error_tx.send(TournamentError {
+        engine_id: engine_config.id.clone(),
+        engine_name: engine_config.name.clone(),
+        game_id: Some(game_id),
+        message,
+        failure_count,
+        disabled,
+    }).await;
+
+    // Games already running play on to the next move check, then abort like any other stop
+    if let (true, Some(should_stop)) = (disabled, stop_on_disable) {
+        *should_stop.lock().await = true;
+        let message = format!("Tournament stopped: {} was disabled after {} failures", engine_config.name, failure_count);
+        error!("{}", message);
+        let _ = error_tx.send(TournamentError {
+            engine_id: engine_config.id.clone(),
+            engine_name: engine_config.name.clone(),
+            game_id: Some(game_id),
+            message,
+            failure_count,
+            disabled,
+        }).await;
+    }
+}
+
+/// Consecutive failures that disable an engine; at least one.
+fn spawn_failure_limit(config: &TournamentConfig) -> u32 {
+    config.spawn_failure_limit.unwrap_or(DEFAULT_SPAWN_FAILURE_LIMIT).max(1)
+}
+
+fn is_engine_disabled(disabled_ids: &HashSet<String>, engine_id: Option<&str>) -> bool {
+    engine_id.map_or(false, |id| disabled_ids.contains(id))
 }
 
 fn forfeit_result(white_disabled: bool, black_disabled: bool) -> (String, Option<String>) {
@@ -808,45 +1802,415 @@ fn forfeit_result(white_disabled: bool, black_disabled: bool) -> (String, Option
     }
 }
 
-fn generate_start_fen(variant: &str) -> String {
+/// Opening book (shuffled when `opening.order` is "random") and pins for `config`,
+/// with the number of invalid lines skipped in the opening file.
+fn load_opening_book(config: &TournamentConfig) -> anyhow::Result<(Vec<String>, HashMap<usize, String>, usize)> {
+    if let Some(sp) = config.opening.chess960_sp.filter(|&sp| sp >= CHESS960_POSITIONS) {
+        return Err(anyhow::anyhow!("Chess960 SP {} is out of range (0-{})", sp, CHESS960_POSITIONS - 1));
+    }
+    if let Some(scope) = config.opening.chess960_scope.as_deref().filter(|scope| !matches!(*scope, "pairing" | "tournament" | "game")) {
+        return Err(anyhow::anyhow!("Unknown Chess960 scope \"{}\"", scope));
+    }
+
+    if let Some(start_fens) = &config.opening.start_fens {
+        return Ok((load_start_fens(config, start_fens)?, opening_pins_of(config)?, 0));
+    }
+
+    let (mut openings, mut skipped) = (Vec::new(), 0);
+    if let Some(ref path) = config.opening.file {
+        (openings, skipped) = load_openings(path, &config.variant, config.opening.depth)?;
+    }
+
+    let opening_pins = opening_pins_of(config)?;
+
+    if let Some(order) = &config.opening.order {
+        if order == "random" {
+            let mut rng = StdRng::seed_from_u64(config.opening.seed.unwrap_or_else(rand::random));
+            openings.shuffle(&mut rng);
+        }
+    }
+
+    Ok((openings, opening_pins, skipped))
+}
+
+fn opening_pins_of(config: &TournamentConfig) -> anyhow::Result<HashMap<usize, String>> {
+    match config.opening.pins_file.as_deref() {
+        Some(path) => load_opening_pins(path),
+        None => Ok(HashMap::new()),
+    }
+}
+
+/// `opening.start_fens`, checked up front and kept in the given order: they are
+/// used like a sequential book, so games pick them by `opening_index`, which the
+/// resume state keeps per game.
+fn load_start_fens(config: &TournamentConfig, start_fens: &[String]) -> anyhow::Result<Vec<String>> {
+    if config.opening.file.is_some() {
+        return Err(anyhow::anyhow!("opening.start_fens and opening.file can't both be set"));
+    }
+    if start_fens.is_empty() {
+        return Err(anyhow::anyhow!("opening.start_fens is empty"));
+    }
+    start_fens.iter().enumerate().map(|(n, fen)| {
+        let fen = fen.trim();
+        if is_valid_start_fen(fen, &config.variant) {
+            Ok(fen.to_string())
+        } else {
+            Err(anyhow::anyhow!("Invalid start FEN {} \"{}\"", n, fen))
+        }
+    }).collect()
+}
+
+/// Start position picked for a scheduled game: pin, then book (both colors of a
+/// swapped pair share an opening, and each cycle replays them), then the configured FEN,
+/// then the fixed Chess960 position. `None` means the position is generated when the game starts.
+fn assigned_start_fen(config: &TournamentConfig, openings: &[String], opening_pins: &HashMap<usize, String>, game: &ScheduleItem) -> Option<String> {
+    if let Some(fen) = opening_pins.get(&game.id) {
+        return Some(fen.clone());
+    }
+    if !openings.is_empty() {
+        return Some(openings[game.opening_index % openings.len()].clone());
+    }
+    configured_fen(config).or_else(|| chess960_sp(config, game).map(chess960_fen))
+}
+
+/// How a game's opening is shown in the schedule: the name of the deepest known
+/// opening its start position and book moves reach, otherwise the opening itself.
+fn opening_label(opening: &str) -> String {
+    let (fen, moves) = split_opening(opening);
+    let moves: Vec<String> = moves.into_iter().map(str::to_string).collect();
+    crate::eco::classify(fen, &moves).map(|(_, name)| name).unwrap_or_else(|| opening.to_string())
+}
+
+fn configured_fen(config: &TournamentConfig) -> Option<String> {
+    config.opening.fen.as_deref().map(str::trim).filter(|f| !f.is_empty()).map(str::to_string)
+}
+
+/// The Chess960 position `game` starts from when it isn't drawn per game:
+/// `opening.chess960_sp`, otherwise, if `opening.chess960_scope` asks for it, one
+/// drawn from `opening.seed` for the whole tournament or for each pairing (both
+/// colors and every cycle).
+fn chess960_sp(config: &TournamentConfig, game: &ScheduleItem) -> Option<u32> {
+    if config.variant != "chess960" {
+        return None;
+    }
+    if let Some(sp) = config.opening.chess960_sp {
+        return Some(sp);
+    }
+    let pairing = ((game.idx_a.min(game.idx_b) as u64) << 32) | game.idx_a.max(game.idx_b) as u64;
+    let stream = match config.opening.chess960_scope.as_deref().unwrap_or("game") {
+        "tournament" => 0,
+        "pairing" => pairing + 1,
+        _ => return None,
+    };
+    let seed = config.opening.seed.unwrap_or_else(rand::random);
+    Some(StdRng::seed_from_u64(seed.rotate_left(32) ^ stream).random_range(0..CHESS960_POSITIONS))
+}
+
+/// Back rank for Scharnagl position `sp`: bishops, then queen, then the knight
+/// pair, with rook-king-rook filling the last three squares.
+fn chess960_fen(sp: u32) -> String {
+    const KNIGHTS: [(usize, usize); 10] = [(0, 1), (0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)];
+    let sp = sp as usize % CHESS960_POSITIONS as usize;
+    let mut rank: [Option<Role>; 8] = [None; 8];
+    rank[2 * (sp % 4) + 1] = Some(Role::Bishop);
+    rank[2 * (sp / 4 % 4)] = Some(Role::Bishop);
+    let place = |rank: &mut [Option<Role>; 8], nth: usize, role: Role| {
+        let file = (0..8).filter(|&file| rank[file].is_none()).nth(nth).expect("free square");
+        rank[file] = Some(role);
+    };
+    place(&mut rank, sp / 16 % 6, Role::Queen);
+    let (n1, n2) = KNIGHTS[sp / 96];
+    // The second knight's index shifts down once the first one is placed.
+    place(&mut rank, n1, Role::Knight);
+    place(&mut rank, n2 - 1, Role::Knight);
+    for role in [Role::Rook, Role::King, Role::Rook] {
+        place(&mut rank, 0, role);
+    }
+    let pieces: String = rank.iter().map(|role| role.expect("every file filled").upper_char()).collect();
+    format!("{}/pppppppp/8/8/8/8/PPPPPPPP/{} w KQkq - 0 1", pieces.to_lowercase(), pieces)
+}
+
+/// Per-game stream from `opening.seed`, so a game's generated position doesn't
+/// depend on the order concurrent games happen to start in.
+fn game_rng(config: &TournamentConfig, game_id: usize) -> StdRng {
+    let seed = config.opening.seed.unwrap_or_else(rand::random);
+    StdRng::seed_from_u64(seed ^ (game_id as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+fn generate_start_fen(variant: &str, rng: &mut impl Rng) -> String {
     if variant == "chess960" {
-        let _pieces = vec![Role::Rook, Role::Knight, Role::Bishop, Role::Queen, Role::King, Role::Bishop, Role::Knight, Role::Rook];
-        let mut rng = rand::rng();
-        let mut dark_squares = vec![0, 2, 4, 6]; let mut light_squares = vec![1, 3, 5, 7];
-        let b1_pos = *dark_squares.choose(&mut rng).expect("Failed to choose dark square");
-        let b2_pos = *light_squares.choose(&mut rng).expect("Failed to choose light square");
-        let mut empty: Vec<usize> = (0..8).filter(|&i| i != b1_pos && i != b2_pos).collect();
-        empty.shuffle(&mut rng);
-        let q_pos = empty[0]; let n1_pos = empty[1]; let n2_pos = empty[2];
-        let mut rem: Vec<usize> = empty[3..].to_vec(); rem.sort();
-        let r1_pos = rem[0]; let k_pos = rem[1]; let r2_pos = rem[2];
-        let mut rank = vec![Role::Pawn; 8];
-        rank[b1_pos] = Role::Bishop; rank[b2_pos] = Role::Bishop; rank[q_pos] = Role::Queen;
-        rank[n1_pos] = Role::Knight; rank[n2_pos] = Role::Knight; rank[r1_pos] = Role::Rook;
-        rank[k_pos] = Role::King; rank[r2_pos] = Role::Rook;
-        let mut fen = String::new();
-        for p in &rank { fen.push(p.upper_char()); }
-        fen.push_str("/pppppppp/8/8/8/8/PPPPPPPP/");
-        for p in &rank { fen.push(p.char()); }
-        fen.push_str(" w KQkq - 0 1");
-        fen
-    } else { "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string() }
-}
-
-fn format_pgn(moves: &[String], result: &str, white_name: &str, black_name: &str, start_fen: &str, event: &str, round: usize) -> String {
+        chess960_fen(rng.random_range(0..CHESS960_POSITIONS))
+    } else { STANDARD_START_FEN.to_string() }
+}
+
+/// Whether a reply is a null move: `bestmove (none)`, which arrives here as an empty
+/// move, or `bestmove 0000`. Only legal when the side to move has no moves at all.
+fn is_null_bestmove(best_move: &str) -> bool {
+    best_move.is_empty() || best_move == "0000"
+}
+
+/// The configured `searchmoves` that are legal in `pos`; the rest are dropped with a
+/// warning, and an empty result means an unrestricted search.
+fn legal_searchmoves(pos: &Board, moves: &[String], game_id: usize) -> Vec<String> {
+    let (legal, illegal): (Vec<String>, Vec<String>) = moves.iter()
+        .map(|mv| mv.trim().to_string())
+        .filter(|mv| !mv.is_empty())
+        .partition(|mv| pos.is_legal_uci(mv));
+    if !illegal.is_empty() {
+        warn!("Game {}: dropping searchmoves not legal in {}: {}", game_id, pos.to_fen_string(), illegal.join(" "));
+    }
+    legal
+}
+
+/// PGN `[Termination]` value for the arbiter's recorded reason: "normal" for
+/// results the rules produce on the board, otherwise how the game was cut short.
+fn termination_tag(diagnostics: &GameDiagnostics) -> Option<String> {
+    let tag = match diagnostics.termination.as_ref()?.reason {
+        TerminationReason::TimeForfeit => "time forfeit",
+        TerminationReason::IllegalMove => "illegal move",
+        TerminationReason::EngineDisconnected => "abandoned",
+        TerminationReason::ProtocolViolation => "rules infraction",
+        TerminationReason::MateAdjudication | TerminationReason::ResignAdjudication | TerminationReason::DrawAdjudication => "adjudication",
+        _ => "normal",
+    };
+    Some(tag.to_string())
+}
+
+/// How the game ended, from the arbiter's own `diagnostics` reason: a board rule
+/// such as checkmate or stalemate, an adjudication or a forfeit.
+fn end_reason(diagnostics: &GameDiagnostics) -> Option<String> {
+    diagnostics.termination.as_ref().map(|termination| termination.reason.to_string())
+}
+
+/// Closing PGN comment for games the rules ended, e.g. "White mates".
+fn ending_comment(reason: TerminationReason, result: &str) -> Option<String> {
+    let comment = match reason {
+        TerminationReason::Checkmate => return Some(format!("{} mates", if result == "1-0" { "White" } else { "Black" })),
+        TerminationReason::Stalemate => "Draw by stalemate",
+        TerminationReason::InsufficientMaterial => "Draw by insufficient mating material",
+        TerminationReason::ThreefoldRepetition => "Draw by threefold repetition",
+        TerminationReason::FiftyMoveRule => "Draw by 50-move rule",
+        TerminationReason::SeventyFiveMoveRule => "Draw by 75-move rule",
+        _ => return None,
+    };
+    Some(comment.to_string())
+}
+
+fn format_search_summary(search: &EngineSearchStats) -> String {
+    let summary = format!("{} nodes, peak {} nps, avg depth {:.1}", search.total_nodes, search.peak_nps, search.avg_depth);
+    // Flag engines that habitually think far past a sensible share of their clock
+    if search.overshot_moves >= MIN_OVERSHOT_MOVES && search.overshot_moves * 2 >= search.moves {
+        format!("{}, overshot its time budget on {} of {} moves", summary, search.overshot_moves, search.moves)
+    } else {
+        summary
+    }
+}
+
+/// Clock after a move that was charged `elapsed`, with `inc` credited as `kind` says.
+fn charge_clock(time_left: i64, elapsed: i64, inc: i64, kind: TimeControlType) -> i64 {
+    match kind {
+        TimeControlType::Fischer => (time_left - elapsed).max(0) + inc,
+        TimeControlType::Bronstein => (time_left - elapsed).max(0) + elapsed.min(inc),
+        TimeControlType::SimpleDelay => (time_left - (elapsed - inc).max(0)).max(0),
+    }
+}
+
+/// Time a move may take before the clock itself starts running down.
+fn free_time(inc: i64, kind: TimeControlType) -> i64 {
+    if kind == TimeControlType::SimpleDelay { inc } else { 0 }
+}
+
+/// `wtime`/`winc` for `go`. UCI only knows Fischer increments, so a simple delay,
+/// spendable before the clock starts, is folded into the clock with no increment.
+/// A Bronstein refund equals the increment whenever the engine thinks at least
+/// that long, so it goes out as one unchanged.
+fn go_clock(time_left: i64, inc: i64, kind: TimeControlType) -> (i64, i64) {
+    match kind {
+        TimeControlType::Fischer | TimeControlType::Bronstein => (time_left, inc),
+        TimeControlType::SimpleDelay => (time_left + inc, 0),
+    }
+}
+
+/// A sensible time for one move: the fixed movetime, otherwise a twentieth of the
+/// clock plus the increment. Moves past `OVERSHOOT_FACTOR` times this count as overshoots.
+fn soft_move_budget(time_left: i64, inc: i64, movetime: Option<u64>) -> i64 {
+    match movetime {
+        Some(ms) => ms as i64,
+        None => time_left.max(0) / MOVE_BUDGET_SHARE + inc,
+    }
+}
+
+/// Event-wide PGN tags from the config, with the GUI's defaults.
+struct PgnHeader<'a> {
+    event: &'a str,
+    site: &'a str,
+    country: Option<&'a str>,
+    extra_tags: &'a [(String, String)],
+}
+
+impl<'a> PgnHeader<'a> {
+    fn from_config(config: &'a TournamentConfig) -> Self {
+        Self {
+            event: config.event_name.as_deref().unwrap_or("CCRL GUI Tournament"),
+            site: config.site_name.as_deref().unwrap_or("CCRL GUI"),
+            country: config.country.as_deref().map(str::trim).filter(|c| !c.is_empty()),
+            extra_tags: config.extra_pgn_tags.as_deref().unwrap_or(&[]),
+        }
+    }
+}
+
+/// One game's PGN tags and movetext; the event-wide tags come from `PgnHeader`.
+struct PgnGame<'a> {
+    moves: &'a [String],
+    result: &'a str,
+    white: &'a str,
+    black: &'a str,
+    start_fen: &'a str,
+    round: usize,
+    termination: Option<&'a str>,
+    final_comment: Option<&'a str>,
+}
+
+/// Tags `format_pgn` writes itself, which `extra_pgn_tags` may not repeat.
+const GENERATED_PGN_TAGS: [&str; 12] = ["Event", "Site", "Date", "Round", "White", "Black", "Result", "ECO", "Opening", "FEN", "SetUp", "Termination"];
+
+/// Engines past the first two, which `TournamentMode::Match` never pairs.
+fn match_mode_benched(config: &TournamentConfig) -> Vec<&str> {
+    if config.mode != TournamentMode::Match {
+        return Vec::new();
+    }
+    config.engines.iter().skip(2).map(|engine| engine.name.as_str()).collect()
+}
+
+/// With `swap_sides` and an odd `games_count` every pairing plays one more game
+/// with one color than the other. Allowed, but worth telling the user about.
+fn unbalanced_colors_warning(config: &TournamentConfig) -> Option<String> {
+    if !config.swap_sides || config.games_count.is_multiple_of(2) {
+        return None;
+    }
+    Some(format!(
+        "games_count {} is odd, so each pairing plays one more game with one color than the other; use an even count for color-balanced pairings",
+        config.games_count
+    ))
+}
+
+/// Extra PGN tag names must be single tokens a PGN reader can parse back.
+fn validate_pgn_tags(config: &TournamentConfig) -> anyhow::Result<()> {
+    for (name, _) in config.extra_pgn_tags.iter().flatten() {
+        if name.is_empty() || name.chars().any(|c| c.is_whitespace() || matches!(c, '[' | ']' | '"')) {
+            return Err(anyhow::anyhow!("Invalid PGN tag name \"{}\"", name));
+        }
+        if GENERATED_PGN_TAGS.iter().any(|tag| tag.eq_ignore_ascii_case(name)) {
+            return Err(anyhow::anyhow!("PGN tag \"{}\" is written by the GUI and can't be set in extra_pgn_tags", name));
+        }
+    }
+    Ok(())
+}
+
+/// How an engine is asked to search, from `EngineConfig.go_style`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GoStyle {
+    /// `go wtime .. btime ..`: the engine manages its own clock
+    Clock,
+    /// `go movetime <allotted>` for engines that ignore wtime/btime
+    Movetime,
+    /// `go infinite`, then `stop` once the allotted time is up
+    InfiniteStop,
+}
+
+impl GoStyle {
+    fn parse(style: Option<&str>) -> Option<Self> {
+        match style.unwrap_or("clock") {
+            "clock" => Some(GoStyle::Clock),
+            "movetime" => Some(GoStyle::Movetime),
+            "infinite_stop" => Some(GoStyle::InfiniteStop),
+            _ => None,
+        }
+    }
+}
+
+fn validate_go_styles(config: &TournamentConfig) -> anyhow::Result<()> {
+    for engine in &config.engines {
+        if GoStyle::parse(engine.go_style.as_deref()).is_none() {
+            return Err(anyhow::anyhow!("Unknown go_style \"{}\" for {}; expected clock, movetime or infinite_stop", engine.go_style.as_deref().unwrap_or_default(), engine.name));
+        }
+    }
+    Ok(())
+}
+
+/// The `go` command for the side to move. Movetime and infinite styles think for
+/// `allotted_ms`, the tournament's movetime or a soft share of the clock.
+fn go_command(style: GoStyle, movetime: Option<u64>, allotted_ms: i64, clock: impl FnOnce() -> String) -> String {
+    match (style, movetime) {
+        (GoStyle::InfiniteStop, _) => "go infinite".to_string(),
+        (_, Some(ms)) => format!("go movetime {}", ms),
+        (GoStyle::Movetime, None) => format!("go movetime {}", allotted_ms.max(1)),
+        (GoStyle::Clock, None) => clock(),
+    }
+}
+
+/// How long a `go movetime ms` search may take before the engine is given up on.
+pub(crate) fn movetime_timeout_ms(ms: u64) -> u64 {
+    ms * MOVETIME_TIMEOUT_FACTOR + MOVETIME_TIMEOUT_GRACE_MS
+}
+
+fn validate_time_control(config: &TournamentConfig) -> anyhow::Result<()> {
+    if config.time_control.movetime_ms == Some(0) {
+        return Err(anyhow::anyhow!("movetime_ms must be at least 1 ms"));
+    }
+    Ok(())
+}
+
+fn validate_tiebreak_order(config: &TournamentConfig) -> anyhow::Result<()> {
+    for name in config.tiebreak_order.iter().flatten() {
+        if crate::stats::Tiebreak::parse(name).is_none() {
+            return Err(anyhow::anyhow!("Unknown tie-break \"{}\"; expected sb, direct_encounter or wins", name));
+        }
+    }
+    Ok(())
+}
+
+fn validate_gauntlet_seeds(config: &TournamentConfig) -> anyhow::Result<()> {
+    if config.mode != TournamentMode::Gauntlet {
+        return Ok(());
+    }
+    for &idx in config.gauntlet_seeds.iter().flatten() {
+        if idx >= config.engines.len() {
+            return Err(anyhow::anyhow!("Gauntlet seed {} is out of range; there are {} engines", idx, config.engines.len()));
+        }
+    }
+    Ok(())
+}
+
+fn format_pgn(game: &PgnGame, header: &PgnHeader) -> String {
+     let PgnGame { moves, result, white: white_name, black: black_name, start_fen, round, termination, final_comment } = *game;
      let mut pgn = String::new();
-     pgn.push_str(&format!("[Event \"{}\"]\n", event));
-     pgn.push_str("[Site \"CCRL GUI\"]\n");
+     pgn.push_str(&format!("[Event \"{}\"]\n", header.event));
+     // PGN puts the IOC country code at the end of the site
+     match header.country {
+         Some(country) => pgn.push_str(&format!("[Site \"{} {}\"]\n", header.site, country)),
+         None => pgn.push_str(&format!("[Site \"{}\"]\n", header.site)),
+     }
      let date = chrono::Local::now().format("%Y.%m.%d");
      pgn.push_str(&format!("[Date \"{}\"]\n", date));
      pgn.push_str(&format!("[Round \"{}\"]\n", round));
      pgn.push_str(&format!("[White \"{}\"]\n", white_name));
      pgn.push_str(&format!("[Black \"{}\"]\n", black_name));
      pgn.push_str(&format!("[Result \"{}\"]\n", result));
-     if start_fen != "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1" {
+     for (name, value) in header.extra_tags {
+         pgn.push_str(&format!("[{} \"{}\"]\n", name, value.replace('\\', "\\\\").replace('"', "\\\"")));
+     }
+     if let Some((eco, opening)) = crate::eco::classify(start_fen, moves) {
+         pgn.push_str(&format!("[ECO \"{}\"]\n", eco));
+         pgn.push_str(&format!("[Opening \"{}\"]\n", opening));
+     }
+     if start_fen != STANDARD_START_FEN {
          pgn.push_str(&format!("[FEN \"{}\"]\n", start_fen));
          pgn.push_str("[SetUp \"1\"]\n");
      }
+     if let Some(termination) = termination {
+         pgn.push_str(&format!("[Termination \"{}\"]\n", termination));
+     }
      pgn.push_str("\n");
 
      for (i, m) in moves.iter().enumerate() {
@@ -856,11 +2220,53 @@ fn format_pgn(moves: &[String], result: &str, white_name: &str, black_name: &str
          pgn.push_str(m);
          pgn.push_str(" ");
      }
+     if let Some(comment) = final_comment {
+         pgn.push_str(&format!("{{{}}} ", comment));
+     }
      pgn.push_str(result);
      pgn.push_str("\n\n");
      pgn
 }
 
+/// Pushes a flushed PGN write to disk when `pgn_sync` asks for durability.
+async fn sync_pgn(handle: &tokio::fs::File, pgn_sync: bool) -> std::io::Result<()> {
+    if pgn_sync { handle.sync_all().await } else { Ok(()) }
+}
+
+async fn append_file_contents(source: &str, destination: &str) -> std::io::Result<()> {
+    let data = match tokio::fs::read(source).await {
+        Ok(data) => data,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    let mut handle = OpenOptions::new().create(true).append(true).open(destination).await?;
+    handle.write_all(&data).await?;
+    handle.flush().await
+}
+
+/// `live.pgn` -> `live_<game_id>.pgn`, so concurrent games never share a file.
+fn live_pgn_file_path(base: &str, game_id: usize) -> PathBuf {
+    let base = Path::new(base);
+    let stem = base.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| "live".to_string());
+    let file_name = match base.extension() {
+        Some(ext) => format!("{}_{}.{}", stem, game_id, ext.to_string_lossy()),
+        None => format!("{}_{}.pgn", stem, game_id),
+    };
+    base.with_file_name(file_name)
+}
+
+/// `{dir}/game_<game_id>.pgn`, one complete game per file, next to its diagnostics.
+fn per_game_pgn_path(dir: &str, game_id: usize) -> PathBuf {
+    Path::new(dir).join(format!("game_{}.pgn", game_id))
+}
+
+async fn write_game_pgn(path: &Path, pgn: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    tokio::fs::write(path, pgn.as_bytes()).await
+}
+
 async fn update_schedule_state(schedule_state: &Arc<Mutex<Vec<ScheduledGame>>>, update: ScheduledGame) {
     let mut schedule = schedule_state.lock().await;
     if let Some(slot) = schedule.iter_mut().find(|game| game.id == update.id) {
@@ -873,6 +2279,7 @@ async fn update_schedule_state(schedule_state: &Arc<Mutex<Vec<ScheduledGame>>>,
 async fn persist_resume_state(
     resume_state_path: &Option<String>,
     schedule_state: &Arc<Mutex<Vec<ScheduledGame>>>,
+    is_paused: &Arc<Mutex<bool>>,
     config: &TournamentConfig,
 ) -> anyhow::Result<()> {
     let path = match resume_state_path.as_ref() {
@@ -880,11 +2287,12 @@ async fn persist_resume_state(
         None => return Ok(()),
     };
     let schedule = schedule_state.lock().await.clone();
+    let paused = *is_paused.lock().await;
     let mut config = config.clone();
     config.resume_from_state = false;
 
     tokio::task::spawn_blocking(move || {
-        let state = TournamentResumeState { config, schedule };
+        let state = TournamentResumeState { config, schedule, paused };
         let json = serde_json::to_string_pretty(&state)?;
         let tmp_path = format!("{}.tmp", path);
         std::fs::write(&tmp_path, json)?;
@@ -895,44 +2303,451 @@ async fn persist_resume_state(
     Ok(())
 }
 
+/// Stops the whole tournament, including games in flight, once the SPRT resolves.
+/// Open-ended runs (`games_count` 0) only stop scheduling instead.
 fn should_stop_for_sprt(config: &TournamentConfig, stats: &TournamentStats) -> bool {
-    if !config.sprt_enabled {
+    if !config.sprt_enabled || plays_until_sprt(config) {
         return false;
     }
+    sprt_resolved(stats)
+}
+
+fn sprt_resolved(stats: &TournamentStats) -> bool {
     matches!(stats.sprt_state.as_str(), "Accept" | "Reject")
 }
 
+fn plays_until_sprt(config: &TournamentConfig) -> bool {
+    config.games_count == 0
+}
+
+/// Enough queued games per pairing to keep every concurrency slot busy.
+fn until_sprt_batch(config: &TournamentConfig) -> u32 {
+    effective_concurrency(config).max(UNTIL_SPRT_BATCH)
+}
+
+/// The engine's own clock for time-odds games, else the tournament's.
+fn engine_time_control(config: &TournamentConfig, engine_idx: usize) -> &TimeControl {
+    config.engines[engine_idx].time_control.as_ref().unwrap_or(&config.time_control)
+}
+
+/// The engine's `Threads` option, 1 when it isn't configured.
+fn engine_threads(engine: &crate::types::EngineConfig) -> u32 {
+    engine.options.iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("Threads"))
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Only the side to move searches, so a game loads as many cores as its busiest engine.
+fn max_engine_threads(config: &TournamentConfig) -> u32 {
+    config.engines.iter().map(engine_threads).max().unwrap_or(1)
+}
+
+fn available_cores() -> Option<u32> {
+    std::thread::available_parallelism().ok().map(|cores| cores.get() as u32)
+}
+
+/// `concurrency` when set; otherwise as many games as the logical cores fit
+/// (4 when the core count can't be read).
+fn effective_concurrency(config: &TournamentConfig) -> u32 {
+    if let Some(concurrency) = config.concurrency {
+        return concurrency.max(1);
+    }
+    available_cores().map_or(4, |cores| (cores / max_engine_threads(config)).max(1))
+}
+
+/// Variants whose centipawn scores mean the same thing as in standard chess.
+fn variant_has_comparable_scores(variant: &str) -> bool {
+    matches!(variant, "standard" | "chess960")
+}
+
+fn adjudication_for_variant(config: &TournamentConfig) -> AdjudicationConfig {
+    if let Some(profile) = config.variant_adjudication.get(&config.variant) {
+        return profile.clone();
+    }
+    let mut adjudication = config.adjudication.clone();
+    if !variant_has_comparable_scores(&config.variant) {
+        adjudication.disable_score_adjudication = true;
+    }
+    adjudication
+}
+
+/// UCI scores are from the side to move; PGN evals and adjudication verdicts are from White's.
+fn white_relative(score: i32, turn: Color) -> i32 {
+    match turn {
+        Color::White => score,
+        Color::Black => -score,
+    }
+}
+
+/// The side both engines' latest White-relative scores say is winning by at least
+/// `threshold`; `None` while either side has no score or they disagree.
+fn resign_agreement(white_eval: Option<i32>, black_eval: Option<i32>, threshold: i32) -> Option<Color> {
+    match (white_eval?, black_eval?) {
+        (w, b) if w >= threshold && b >= threshold => Some(Color::White),
+        (w, b) if w <= -threshold && b <= -threshold => Some(Color::Black),
+        _ => None,
+    }
+}
+
+/// Consecutive moves whose White-relative verdict favors the same side. A verdict
+/// for the other side starts a new streak, so contradicting engines never add up.
+#[derive(Clone, Copy, Debug, Default)]
+struct WinningStreak {
+    moves: u32,
+    white_winning: bool,
+}
+
+impl WinningStreak {
+    /// Counts the latest move's verdict; `None` (or 0) breaks the streak.
+    fn record(&mut self, white_view: Option<i32>) {
+        match white_view.filter(|&value| value != 0) {
+            Some(value) if self.moves > 0 && (value > 0) == self.white_winning => self.moves += 1,
+            Some(value) => *self = WinningStreak { moves: 1, white_winning: value > 0 },
+            None => self.moves = 0,
+        }
+    }
+
+    fn winner(&self) -> Color {
+        if self.white_winning { Color::White } else { Color::Black }
+    }
+}
+
+/// Book line for the `game_idx`-th game of a pairing: both colors of a swapped
+/// pair share one line.
+fn opening_index(config: &TournamentConfig, game_idx: u32) -> usize {
+    if config.swap_sides { (game_idx / 2) as usize } else { game_idx as usize }
+}
+
+/// White is `idx_b` on odd games when swapping sides, and every other cycle
+/// flips that so each cycle pair gives both engines both colors. `stagger` is
+/// `idx_b`'s entry from `gauntlet_staggers`.
+fn colors_swapped(config: &TournamentConfig, stagger: bool, cycle: u32, game_idx: u32) -> bool {
+    let odd_game = config.swap_sides && !game_idx.is_multiple_of(2);
+    let odd_cycle = !cycle.is_multiple_of(2);
+    (odd_game != odd_cycle) != stagger
+}
+
+/// Sorted seed indices of a gauntlet, already checked to be in range; the first
+/// engine when none are given.
+fn gauntlet_seeds(config: &TournamentConfig) -> Vec<usize> {
+    let mut seeds = config.gauntlet_seeds.clone().unwrap_or_else(|| vec![0]);
+    seeds.sort_unstable();
+    seeds.dedup();
+    seeds
+}
+
+/// With an odd number of games per opponent the seed gets one color more against
+/// each of them, so every other opponent starts with White instead to even the
+/// seed's colors out across the gauntlet. One flag per engine, set on every
+/// second non-seed.
+fn gauntlet_staggers(config: &TournamentConfig) -> Vec<bool> {
+    let mut staggers = vec![false; config.engines.len()];
+    if config.mode != TournamentMode::Gauntlet || !config.swap_sides || config.games_count.is_multiple_of(2) {
+        return staggers;
+    }
+    let seeds = gauntlet_seeds(config);
+    let opponents = (0..config.engines.len()).filter(|idx| !seeds.contains(idx));
+    for (nth, idx) in opponents.enumerate() {
+        staggers[idx] = !nth.is_multiple_of(2);
+    }
+    staggers
+}
+
+/// Maps a game id back to its pairing. Ids are handed out pairing by pairing in
+/// `generate_pairings` order (cycles included), one pass per repetition, while each
+/// pairing's game index keeps counting up, so `pairings` must come from the same config.
 fn compute_game_mapping(
-    pairings: &[(usize, usize)],
+    pairings: &[(usize, usize, u32)],
     games_count: u32,
     game_id: usize,
-) -> Option<(usize, usize, u32)> {
+) -> Option<(usize, usize, u32, u32)> {
     let games_per_pairing = games_count as usize;
-    if games_per_pairing == 0 {
+    if games_per_pairing == 0 || pairings.is_empty() {
         return None;
     }
     let index = game_id.checked_sub(1)?;
-    let pairing_index = index / games_per_pairing;
-    let game_index = index % games_per_pairing;
-    let (idx_a, idx_b) = *pairings.get(pairing_index)?;
-    Some((idx_a, idx_b, game_index as u32))
+    let games_per_repetition = games_per_pairing * pairings.len();
+    let (repetition, index) = (index / games_per_repetition, index % games_per_repetition);
+    let (idx_a, idx_b, cycle) = pairings[index / games_per_pairing];
+    let game_index = repetition * games_per_pairing + index % games_per_pairing;
+    Some((idx_a, idx_b, cycle, game_index as u32))
+}
+
+/// The repetition, from 1, that a game id was scheduled in; see `compute_game_mapping`.
+fn game_repetition(pairings: &[(usize, usize, u32)], games_count: u32, game_id: usize) -> u32 {
+    let games_per_repetition = games_count as usize * pairings.len();
+    if games_per_repetition == 0 {
+        return 1;
+    }
+    (game_id.saturating_sub(1) / games_per_repetition) as u32 + 1
 }
 
-async fn initialize_engine(engine: &AsyncEngine, config: &crate::types::EngineConfig, variant: &str) -> anyhow::Result<()> {
+/// Whether two engine entries run the same executable from the same directory,
+/// so relative hash, learning or log files of one process would clobber the other's.
+fn engines_share_files(a: &crate::types::EngineConfig, b: &crate::types::EngineConfig) -> bool {
+    let resolve = |path: &str| std::fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+    let dir = |engine: &crate::types::EngineConfig| match engine.working_directory.as_deref() {
+        Some(dir) => resolve(dir),
+        None => std::env::current_dir().unwrap_or_default(),
+    };
+    resolve(&a.path) == resolve(&b.path) && dir(a) == dir(b)
+}
+
+/// Directory engine `engine_idx` runs in for `game_id`: a fresh temp directory
+/// when `isolate_engine_dirs` applies to it and its opponent, else its configured one.
+fn engine_working_dir(config: &TournamentConfig, game_id: usize, engine_idx: usize, opponent_idx: usize) -> Option<PathBuf> {
+    let engine = &config.engines[engine_idx];
+    if config.isolate_engine_dirs && engines_share_files(engine, &config.engines[opponent_idx]) {
+        let name = format!("game_{}_engine_{}_{}", game_id, engine_idx, std::process::id());
+        return Some(std::env::temp_dir().join("mini-tcec").join(name));
+    }
+    engine.working_directory.as_ref().map(PathBuf::from)
+}
+
+async fn spawn_engine_in(engine: &crate::types::EngineConfig, working_dir: Option<&Path>, isolated: bool) -> anyhow::Result<AsyncEngine> {
+    if let (true, Some(dir)) = (isolated, working_dir) {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    let process = AsyncEngine::spawn_with_buffer(&engine.path, working_dir, output_buffer_lines(engine)).await?;
+    info!("Spawned engine {} ({})", engine.name, engine.path);
+    Ok(process)
+}
+
+pub(crate) fn output_buffer_lines(engine: &crate::types::EngineConfig) -> usize {
+    engine.output_buffer_lines.unwrap_or(DEFAULT_OUTPUT_BUFFER_LINES)
+}
+
+/// Forwards an engine's `info` lines to the UI for one game and warns, without
+/// forfeiting anything, when its searches look unhealthy. `info string` text goes
+/// out as engine log lines, and to `<log_dir>/<engine name>.log` when set.
+#[allow(clippy::too_many_arguments)]
+fn spawn_info_listener(
+    engine: &AsyncEngine,
+    engine_config: &crate::types::EngineConfig,
+    engine_idx: usize,
+    game_id: usize,
+    health: EngineHealthConfig,
+    log_dir: Option<&Path>,
+    stats_tx: &mpsc::Sender<EngineStats>,
+    error_tx: &mpsc::Sender<TournamentError>,
+    engine_log_tx: &mpsc::Sender<EngineLogLine>,
+    should_stop: &Arc<Mutex<bool>>,
+    game_span: &tracing::Span,
+) {
     let mut rx = engine.stdout_broadcast.subscribe();
+    let engine = engine.clone();
+    let (engine_id, engine_name) = (engine_config.id.clone(), engine_config.name.clone());
+    let (stats_tx, error_tx, engine_log_tx, should_stop) = (stats_tx.clone(), error_tx.clone(), engine_log_tx.clone(), should_stop.clone());
+    let log_path = log_dir.map(|dir| engine_log_path(dir, &engine_name));
+    let mut log_file = None;
+    let mut monitor = HealthMonitor::new(health);
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(line) => {
+                    if *should_stop.lock().await { break; }
+                    if line.starts_with("info") {
+                        if let Some(message) = info_string(&line) {
+                            if let Some(path) = log_path.as_deref() {
+                                if let Err(err) = append_engine_log(&mut log_file, path, game_id, message).await {
+                                    warn!("Failed to write engine log {}: {}", path.display(), err);
+                                }
+                            }
+                            let _ = engine_log_tx.send(EngineLogLine {
+                                engine_id: engine_id.clone(),
+                                engine_name: engine_name.clone(),
+                                game_id,
+                                message: message.to_string(),
+                            }).await;
+                        }
+                        if let Some(stats) = parse_info_with_id(&line, engine_idx, game_id) {
+                            monitor.record_info(&line, &stats);
+                            let _ = stats_tx.send(stats).await;
+                        }
+                    } else if line.starts_with("bestmove") {
+                        for symptom in monitor.finish_search() {
+                            warn!("Game {}: {} {}", game_id, engine_name, symptom);
+                            let _ = error_tx.send(TournamentError {
+                                engine_id: engine_id.clone(),
+                                engine_name: engine_name.clone(),
+                                game_id: Some(game_id),
+                                message: format!("{} {}", engine_name, symptom),
+                                failure_count: 0,
+                                disabled: false,
+                            }).await;
+                        }
+                    }
+                },
+                Err(broadcast::error::RecvError::Lagged(count)) => {
+                    engine.record_lag(count);
+                    warn!("Game {}: info listener for engine {} skipped {} lines ({} total)", game_id, engine_idx, count, engine.lagged_lines());
+                },
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }.instrument(game_span.clone()));
+}
+
+/// One log file per engine name, with anything unsafe in a file name replaced.
+fn engine_log_path(dir: &Path, engine_name: &str) -> PathBuf {
+    let file_name: String = engine_name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '_' })
+        .collect();
+    dir.join(format!("{}.log", file_name))
+}
+
+/// Appends one line to `path`, opening it on first use so a listener keeps one
+/// handle for the whole game instead of reopening the file per line.
+async fn append_engine_log(file: &mut Option<tokio::fs::File>, path: &Path, game_id: usize, message: &str) -> std::io::Result<()> {
+    let file = match file {
+        Some(file) => file,
+        None => {
+            if let Some(dir) = path.parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            file.insert(OpenOptions::new().create(true).append(true).open(path).await?)
+        }
+    };
+    file.write_all(format!("[game {}] {}\n", game_id, message).as_bytes()).await?;
+    file.flush().await
+}
+
+async fn remove_isolated_dirs(isolated: bool, dirs: [Option<PathBuf>; 2]) {
+    if !isolated {
+        return;
+    }
+    for dir in dirs.into_iter().flatten() {
+        let _ = tokio::fs::remove_dir_all(dir).await;
+    }
+}
+
+/// Rough share of a game that is done after `plies`, for progress bars. It grows
+/// with every move but never reaches 1, so even endless games keep nudging the bar.
+pub fn estimated_game_progress(plies: usize) -> f64 {
+    plies as f64 / (plies as f64 + PROGRESS_HALF_PLIES)
+}
+
+/// Sends the thinking side's running clock every `CLOCK_TICK_MS` until aborted,
+/// so the display counts down during a long think. The first `free_ms` of the
+/// think (a simple delay) leave the clock alone.
+fn spawn_clock_ticker(
+    time_update_tx: mpsc::Sender<TimeUpdate>,
+    game_id: usize,
+    turn: Color,
+    white_time: i64,
+    black_time: i64,
+    free_ms: i64,
+    start: Instant,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(CLOCK_TICK_MS));
+        loop {
+            interval.tick().await;
+            let elapsed = (start.elapsed().as_millis() as i64 - free_ms).max(0);
+            let (white_time, black_time) = match turn {
+                Color::White => ((white_time - elapsed).max(0), black_time),
+                Color::Black => (white_time, (black_time - elapsed).max(0)),
+            };
+            let update = TimeUpdate { white_time: white_time as u64, black_time: black_time as u64, game_id };
+            if time_update_tx.send(update).await.is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Spawns and handshakes a replacement for `engine_idx`, which crashed mid-game
+/// against `opponent_idx`, registering it so stop/abort reach it like the original.
+async fn restart_engine(
+    config: &TournamentConfig,
+    engine_idx: usize,
+    opponent_idx: usize,
+    game_id: usize,
+    active_engines: &Arc<Mutex<Vec<AsyncEngine>>>,
+    active_games: &Arc<Mutex<HashMap<usize, ActiveGame>>>,
+) -> anyhow::Result<AsyncEngine> {
+    let engine_config = &config.engines[engine_idx];
+    let working_dir = engine_working_dir(config, game_id, engine_idx, opponent_idx);
+    let engine = AsyncEngine::spawn_with_buffer(&engine_config.path, working_dir.as_deref(), output_buffer_lines(engine_config)).await?;
+    active_engines.lock().await.push(engine.clone());
+    if let Some(active_game) = active_games.lock().await.get_mut(&game_id) {
+        active_game.engines.push(engine.clone());
+    }
+    let nodestime = config.time_control.nodestime.map(|n| n.max(1));
+    let opponent = config.send_uci_opponent.then(|| config.engines[opponent_idx].name.as_str());
+    initialize_engine(&engine, engine_config, engine_idx, &config.variant, nodestime, opponent).await?;
+    Ok(engine)
+}
+
+/// Clock time a search that ended without a move still costs: its thinking, or
+/// its nodes at the `nodestime` rate. Nothing under a fixed movetime.
+fn unfinished_search_cost(thinking: Duration, nodes: u64, nodestime: Option<u64>, movetime: Option<u64>) -> i64 {
+    match (movetime, nodestime) {
+        (Some(_), _) => 0,
+        (None, Some(nodes_per_ms)) => (nodes / nodes_per_ms) as i64,
+        (None, None) => thinking.as_millis() as i64,
+    }
+}
+
+/// Every `setoption` the arbiter sends during the handshake, in order.
+fn setoption_commands(config: &crate::types::EngineConfig, variant: &str, nodestime: Option<u64>) -> Vec<String> {
+    let mut options: Vec<(&str, String)> = config.options.iter()
+        .filter(|(name, _)| config.limit_elo.is_none() || !is_strength_option(name))
+        .map(|(name, value)| (name.as_str(), value.clone()))
+        .collect();
+    if let Some(elo) = config.limit_elo {
+        options.push(("UCI_LimitStrength", "true".to_string()));
+        options.push(("UCI_Elo", elo.to_string()));
+    }
+    // Some engines ignore UCI_Elo unless strength limiting is already on
+    options.sort_by_key(|(name, _)| !name.eq_ignore_ascii_case("UCI_LimitStrength"));
+    let mut commands: Vec<String> = options.iter()
+        .map(|(name, value)| format!("setoption name {} value {}", name, value))
+        .collect();
+    // Handle Chess960 option if needed
+    if variant == "chess960" {
+        commands.push("setoption name UCI_Chess960 value true".into());
+    }
+    if let Some(nodestime) = nodestime {
+        commands.push(format!("setoption name nodestime value {}", nodestime));
+    }
+    commands
+}
+
+fn is_strength_option(name: &str) -> bool {
+    name.eq_ignore_ascii_case("UCI_LimitStrength") || name.eq_ignore_ascii_case("UCI_Elo")
+}
+
+/// `UCI_Opponent` for an engine opponent: no title, no rating, type `computer`.
+fn uci_opponent_command(opponent: &str) -> String {
+    format!("setoption name UCI_Opponent value none none computer {}", opponent)
+}
+
+/// Sends `uci` and returns the options the engine declares before `uciok`, waiting
+/// at most its `init_timeout_ms`. Output from before `uci` is skipped.
+async fn uci_handshake(engine: &AsyncEngine, config: &crate::types::EngineConfig, engine_idx: usize) -> anyhow::Result<(Vec<UciOption>, broadcast::Receiver<String>)> {
+    let init_timeout = init_timeout(config);
+    let (mut rx, banner) = engine.drain_startup_output().await;
+    if !banner.is_empty() {
+        info!("{} printed {} line(s) before uci; ignored", config.name, banner.len());
+    }
     engine.send("uci".into()).await?;
+    let mut options = Vec::new();
 
     // Wait for uciok
     let uciok_future = async {
         loop {
             match rx.recv().await {
-                Ok(line) => {
-                    if line.trim() == "uciok" {
-                        return Ok(());
-                    }
+                Ok(line) => match crate::uci::handshake_line(&line) {
+                    HandshakeLine::UciOk => return Ok(()),
+                    HandshakeLine::Option(opt) => options.push(opt),
+                    HandshakeLine::IdName(_) | HandshakeLine::IdAuthor(_) | HandshakeLine::Chatter => {}
                 },
-                Err(broadcast::error::RecvError::Lagged(_)) => {
-                    println!("Warning: Lagged waiting for uciok from {}", config.name);
+                Err(broadcast::error::RecvError::Lagged(count)) => {
+                    engine.record_lag(count);
+                    warn!("Lagged waiting for uciok from {}", config.name);
                     continue;
                 },
                 Err(broadcast::error::RecvError::Closed) => {
@@ -942,17 +2757,41 @@ async fn initialize_engine(engine: &AsyncEngine, config: &crate::types::EngineCo
         }
     };
 
-    timeout(Duration::from_secs(10), uciok_future).await
-        .map_err(|_| anyhow::anyhow!("Timeout waiting for uciok from {}", config.name))??;
+    timeout(init_timeout, uciok_future).await
+        .map_err(|_| EngineInitError {
+            engine_idx,
+            message: format!("{} never sent uciok (waited {} ms)", config.name, init_timeout.as_millis()),
+        })??;
+    Ok((options, rx))
+}
 
-    // Send options
-    for (name, value) in &config.options {
-        engine.send(format!("setoption name {} value {}", name, value)).await?;
-    }
+fn init_timeout(config: &crate::types::EngineConfig) -> Duration {
+    Duration::from_millis(config.init_timeout_ms.unwrap_or(10_000))
+}
 
-    // Handle Chess960 option if needed
-    if variant == "chess960" {
-        engine.send("setoption name UCI_Chess960 value true".into()).await?;
+/// `opponent` is sent as `UCI_Opponent` when the engine declares that option.
+pub(crate) async fn initialize_engine(engine: &AsyncEngine, config: &crate::types::EngineConfig, engine_idx: usize, variant: &str, nodestime: Option<u64>, opponent: Option<&str>) -> anyhow::Result<()> {
+    let init_timeout = init_timeout(config);
+    let (options, mut rx) = uci_handshake(engine, config, engine_idx).await?;
+    let declares = |name: &str| options.iter().any(|opt| opt.name.eq_ignore_ascii_case(name));
+    let supports_nodestime = declares("nodestime");
+    let supports_opponent = declares("UCI_Opponent");
+
+    if nodestime.is_some() && !supports_nodestime {
+        return Err(anyhow::anyhow!("{} does not support the nodestime option", config.name));
+    }
+    let strength_options = config.options.iter().map(|(name, _)| name.as_str())
+        .chain(config.limit_elo.map(|_| ["UCI_LimitStrength", "UCI_Elo"]).into_iter().flatten());
+    for name in strength_options.filter(|name| is_strength_option(name)) {
+        if !declares(name) {
+            warn!("{} does not declare {}; the handicap may have no effect", config.name, name);
+        }
+    }
+    for command in setoption_commands(config, variant, nodestime) {
+        engine.send(command).await?;
+    }
+    if let Some(opponent) = opponent.filter(|_| supports_opponent) {
+        engine.send(uci_opponent_command(opponent)).await?;
     }
 
     engine.send("isready".into()).await?;
@@ -966,8 +2805,9 @@ async fn initialize_engine(engine: &AsyncEngine, config: &crate::types::EngineCo
                         return Ok(());
                     }
                 },
-                Err(broadcast::error::RecvError::Lagged(_)) => {
-                    println!("Warning: Lagged waiting for readyok from {}", config.name);
+                Err(broadcast::error::RecvError::Lagged(count)) => {
+                    engine.record_lag(count);
+                    warn!("Lagged waiting for readyok from {}", config.name);
                     continue;
                 },
                 Err(broadcast::error::RecvError::Closed) => {
@@ -977,8 +2817,11 @@ async fn initialize_engine(engine: &AsyncEngine, config: &crate::types::EngineCo
         }
     };
 
-    timeout(Duration::from_secs(10), readyok_future).await
-        .map_err(|_| anyhow::anyhow!("Timeout waiting for readyok from {}", config.name))??;
+    timeout(init_timeout, readyok_future).await
+        .map_err(|_| EngineInitError {
+            engine_idx,
+            message: format!("{} never sent readyok (waited {} ms)", config.name, init_timeout.as_millis()),
+        })??;
 
     engine.send("ucinewgame".into()).await?;
     Ok(())
@@ -992,49 +2835,112 @@ async fn play_game_static(
     start_fen: &str,
     config: &TournamentConfig,
     game_update_tx: &mpsc::Sender<GameUpdate>,
+    time_update_tx: &mpsc::Sender<TimeUpdate>,
+    live_pgn_tx: Option<&mpsc::Sender<LivePgnUpdate>>,
     should_stop: &Arc<Mutex<bool>>,
+    abort_flag: &Arc<Mutex<bool>>,
     is_paused: &Arc<Mutex<bool>>,
-    game_id: usize
-) -> anyhow::Result<(String, Vec<String>)> {
+    active_engines: &Arc<Mutex<Vec<AsyncEngine>>>,
+    active_games: &Arc<Mutex<HashMap<usize, ActiveGame>>>,
+    game_id: usize,
+    regenerable_start: bool,
+) -> anyhow::Result<PlayedGame> {
+    // Replaced in place when a crashed engine is restarted
+    let mut white_engine = white_engine.clone();
+    let mut black_engine = black_engine.clone();
+    let max_restarts = config.max_in_game_restarts.unwrap_or(0);
+    let mut white_restarts = 0;
+    let mut black_restarts = 0;
     let is_960 = config.variant == "chess960";
+    let (start_fen, book_moves) = split_opening(start_fen);
     let mut pos: Board = if is_960 {
-         let setup = Fen::from_ascii(start_fen.as_bytes())?;
-         let pos_960: Chess = setup.into_position(CastlingMode::Chess960)?;
-         Board::Chess960(pos_960)
+         let parsed = Fen::from_ascii(start_fen.as_bytes())
+             .map_err(anyhow::Error::from)
+             .and_then(|setup| setup.into_position::<Chess>(CastlingMode::Chess960).map_err(anyhow::Error::from));
+         match parsed {
+             Ok(pos_960) => Board::Chess960(pos_960),
+             Err(_) if regenerable_start => return Err(StartPositionRejected.into()),
+             Err(e) => return Err(e),
+         }
     } else {
          let setup = Fen::from_ascii(start_fen.as_bytes())?;
          let pos_std: Chess = setup.into_position(CastlingMode::Standard)?;
          Board::Standard(pos_std)
     };
 
-    // Initialize engines with proper UCI handshake
-    initialize_engine(white_engine, &config.engines[white_idx], &config.variant).await?;
-    initialize_engine(black_engine, &config.engines[black_idx], &config.variant).await?;
-
-    let mut white_time = config.time_control.base_ms as i64;
-    let mut black_time = config.time_control.base_ms as i64;
-    let inc = config.time_control.inc_ms as i64;
+    // Both engines have been through the UCI handshake by now
+    let nodestime = config.time_control.nodestime.map(|n| n.max(1));
+    info!("Game {}: started from {}", game_id, start_fen);
+
+    let white_control = engine_time_control(config, white_idx);
+    let black_control = engine_time_control(config, black_idx);
+    let mut white_time = white_control.base_ms as i64;
+    let mut black_time = black_control.base_ms as i64;
+    let (white_inc, black_inc) = (white_control.inc_ms as i64, black_control.inc_ms as i64);
+    let (white_kind, black_kind) = (white_control.control_type, black_control.control_type);
+    let move_overhead = if nodestime.is_some() { 0 } else { config.time_control.move_overhead_ms as i64 };
+    let movetime = config.time_control.movetime_ms.map(|ms| ms.max(1));
+    let pv_plies = config.pv_plies.unwrap_or(DEFAULT_PV_PLIES) as usize;
+    let adjudication = adjudication_for_variant(config);
     let mut moves_history: Vec<String> = Vec::new();
+    let mut diagnostics = GameDiagnostics {
+        game_id,
+        white: config.engines[white_idx].name.clone(),
+        black: config.engines[black_idx].name.clone(),
+        start_fen: start_fen.to_string(),
+        white_options: setoption_commands(&config.engines[white_idx], &config.variant, nodestime),
+        black_options: setoption_commands(&config.engines[black_idx], &config.variant, nodestime),
+        ..Default::default()
+    };
 
-    let mut consec_resign_moves = 0;
+    let mut resign_streak = WinningStreak::default();
+    // Latest centipawn score per side, from White's view
+    let mut white_eval: Option<i32> = None;
+    let mut black_eval: Option<i32> = None;
     let mut consec_draw_moves = 0;
+    let mut mate_streak = WinningStreak::default();
+    let mut white_search = EngineSearchStats::default();
+    let mut black_search = EngineSearchStats::default();
+    // Latest mate claim per side, relative to that side (UCI convention)
+    let mut white_mate: Option<i32> = None;
+    let mut black_mate: Option<i32> = None;
     let mut game_result;
-    let mut repetition_counts: HashMap<String, u32> = HashMap::new();
-    let mut halfmove_clock: u32 = start_fen
-        .split_whitespace()
-        .nth(4)
-        .and_then(|value| value.parse().ok())
-        .unwrap_or(0);
-
-    let repetition_key = |fen: &str| -> String {
-        fen.split_whitespace().take(4).collect::<Vec<_>>().join(" ")
-    };
-    repetition_counts.insert(repetition_key(&pos.to_fen_string()), 1);
+    let mut repetition_counts: HashMap<u128, u32> = HashMap::new();
+    // Seeded from the parsed setup so FENs/EPDs without clock fields start at 0
+    let mut halfmove_clock: u32 = pos.halfmoves();
+
+    repetition_counts.insert(pos.repetition_key(), 1);
+
+    // The first update carries the start position, so boards can set up non-standard starts
+    let _ = game_update_tx.send(GameUpdate {
+        fen: pos.to_fen_string(), last_move: None, pv_moves: None, white_time: white_time as u64, black_time: black_time as u64,
+        move_number: 1, result: None, white_engine_idx: white_idx, black_engine_idx: black_idx,
+        game_id, termination: None, end_reason: None, start_fen: Some(start_fen.to_string())
+    }).await;
+
+    // An opening line's moves are played for the engines before they take over
+    for uci in &book_moves {
+        let book_move = pos.uci_move(uci)
+            .ok_or_else(|| anyhow::anyhow!("Opening move {} is illegal in {}", uci, pos.to_fen_string()))?;
+        pos.play_unchecked(&book_move);
+        moves_history.push(uci.to_string());
+        halfmove_clock = if book_move.is_zeroing() { 0 } else { halfmove_clock.saturating_add(1) };
+        *repetition_counts.entry(pos.repetition_key()).or_insert(0) += 1;
+        let _ = game_update_tx.send(GameUpdate {
+            fen: pos.to_fen_string(), last_move: Some(uci.to_string()), pv_moves: None, white_time: white_time as u64, black_time: black_time as u64,
+            move_number: moves_history.len().div_ceil(2) as u32, result: None, white_engine_idx: white_idx, black_engine_idx: black_idx,
+            game_id, termination: None, end_reason: None, start_fen: None
+        }).await;
+    }
+    let book_plies = moves_history.len();
 
-    loop {
+    'moves: loop {
         if *should_stop.lock().await {
             return Err(anyhow::anyhow!("stopped"));
         }
+        if *abort_flag.lock().await {
+            return Err(anyhow::anyhow!("aborted"));
+        }
         if *is_paused.lock().await { sleep(Duration::from_millis(100)).await; continue; }
 
         let current_move_num = (moves_history.len() / 2) + 1;
@@ -1047,10 +2953,11 @@ async fn play_game_static(
 
         if material_draw {
              game_result = "1/2-1/2".to_string();
+             diagnostics.terminate(TerminationReason::InsufficientMaterial, pos.to_fen_string());
              let _ = game_update_tx.send(GameUpdate {
-                fen: pos.to_fen_string(), last_move: None, white_time: white_time as u64, black_time: black_time as u64,
+                fen: pos.to_fen_string(), last_move: None, pv_moves: None, white_time: white_time as u64, black_time: black_time as u64,
                 move_number: current_move_num as u32, result: Some(game_result.clone()), white_engine_idx: white_idx, black_engine_idx: black_idx,
-                game_id
+                game_id, termination: termination_tag(&diagnostics), end_reason: end_reason(&diagnostics), start_fen: None
             }).await;
             break;
         }
@@ -1063,122 +2970,364 @@ async fn play_game_static(
                 shakmaty::Outcome::Draw => "1/2-1/2",
             };
             game_result = result_str.to_string();
+            let reason = match outcome {
+                shakmaty::Outcome::Decisive { .. } => TerminationReason::Checkmate,
+                shakmaty::Outcome::Draw if pos.is_stalemate() => TerminationReason::Stalemate,
+                shakmaty::Outcome::Draw => TerminationReason::InsufficientMaterial,
+            };
+            diagnostics.terminate(reason, pos.to_fen_string());
             let _ = game_update_tx.send(GameUpdate {
-                fen: pos.to_fen_string(), last_move: None, white_time: white_time as u64, black_time: black_time as u64,
+                fen: pos.to_fen_string(), last_move: None, pv_moves: None, white_time: white_time as u64, black_time: black_time as u64,
                 move_number: current_move_num as u32, result: Some(result_str.to_string()), white_engine_idx: white_idx, black_engine_idx: black_idx,
-                game_id
+                game_id, termination: termination_tag(&diagnostics), end_reason: end_reason(&diagnostics), start_fen: None
             }).await;
             break;
         }
 
         let turn = pos.turn();
-        let (active_engine, _time_left, _other_time) = match turn {
-            Color::White => (white_engine, white_time, black_time),
-            Color::Black => (black_engine, black_time, white_time),
-        };
+        let time_left = if turn == Color::White { white_time } else { black_time };
+        let (inc, kind) = match turn { Color::White => (white_inc, white_kind), Color::Black => (black_inc, black_kind) };
+        let go_style = GoStyle::parse(config.engines[match turn { Color::White => white_idx, Color::Black => black_idx }].go_style.as_deref()).unwrap_or(GoStyle::Clock);
+        let allotted_ms = soft_move_budget((time_left - move_overhead).max(0), inc, movetime);
 
         let mut pos_cmd = format!("position fen {} moves", start_fen);
         for m in &moves_history { pos_cmd.push_str(" "); pos_cmd.push_str(m); }
-        active_engine.send(pos_cmd).await?;
-
-        let go_cmd = format!("go wtime {} btime {} winc {} binc {}", white_time, black_time, inc, inc);
-        let mut active_rx = active_engine.stdout_broadcast.subscribe();
-        active_engine.send(go_cmd).await?;
+        // Engines see their clock net of the overhead so they budget for the latency too
+        let go_cmd = go_command(go_style, movetime, allotted_ms, || {
+            let (wtime, winc) = go_clock((white_time - move_overhead).max(0), white_inc, white_kind);
+            let (btime, binc) = go_clock((black_time - move_overhead).max(0), black_inc, black_kind);
+            format!("go wtime {} btime {} winc {} binc {}", wtime, btime, winc, binc)
+        });
+        let go_cmd = match config.opening.searchmoves.as_deref() {
+            Some(moves) if moves_history.len() == book_plies => {
+                let legal = legal_searchmoves(&pos, moves, game_id);
+                if legal.is_empty() { go_cmd } else { format!("{} searchmoves {}", go_cmd, legal.join(" ")) }
+            }
+            _ => go_cmd,
+        };
 
-        let start = Instant::now();
         let mut best_move_str = String::new();
-        let mut move_score: Option<i32> = None;
-
-        let time_left = if turn == Color::White { white_time } else { black_time };
-        // Timeout: Remaining time + 5s buffer, capped at 24h
-        let timeout_ms = (time_left + 5000).max(5000) as u64;
+        let mut move_score: Option<i32>;
+        let mut move_mate: Option<i32>;
+        let mut move_nodes: u64;
+        let mut move_depth: u32;
+        let mut move_nps: u64;
+        let mut move_pv: Vec<String>;
+        let mut recent_output: VecDeque<String> = VecDeque::with_capacity(STALL_LOG_LINES);
+
+        // Timeout: Remaining time + 5s buffer, capped at 24h; the move overhead never shortens it
+        let timeout_ms = (time_left + free_time(inc, kind) + 5000).max(5000) as u64;
         let max_cap_ms = 24 * 60 * 60 * 1000;
-        let timeout_duration = Duration::from_millis(timeout_ms.min(max_cap_ms));
-
-        let bestmove_future = async {
-            loop {
-                 match active_rx.recv().await {
-                     Ok(line) => {
-                        if line.starts_with("info") {
-                            if let Some(stats) = parse_info(&line, 0) {
-                                if let Some(cp) = stats.score_cp {
-                                     move_score = Some(cp);
-                                } else if let Some(mate) = stats.score_mate {
-                                     move_score = Some(if mate > 0 { 30000 - mate } else { -30000 - mate });
+        // In nodestime mode the clock is virtual, so wall-clock speed must not forfeit anyone
+        let timeout_duration = if let Some(ms) = movetime {
+            // No clock to run out; only guard against an engine that ignores movetime
+            Duration::from_millis(movetime_timeout_ms(ms).min(max_cap_ms))
+        } else if nodestime.is_some() {
+            Duration::from_millis(max_cap_ms)
+        } else {
+            Duration::from_millis(timeout_ms.min(max_cap_ms))
+        };
+
+        // A crashed engine is restarted and asked again while its restart budget lasts
+        let (bestmove_result, start) = loop {
+            let active_engine = match turn { Color::White => &white_engine, Color::Black => &black_engine };
+            let mut active_rx = active_engine.stdout_broadcast.subscribe();
+            let engine_alive = active_engine.is_alive.clone();
+            let searching_engine = active_engine.clone();
+            // `isready` fences off output from before this `go`: a bestmove ahead of the
+            // readyok answers a search nobody asked for
+            let mut sent = Ok(());
+            for command in [pos_cmd.clone(), "isready".to_string(), go_cmd.clone()] {
+                sent = active_engine.send(command).await;
+                if sent.is_err() { break; }
+            }
+            let start = Instant::now();
+            best_move_str.clear();
+            (move_score, move_mate, move_nodes, move_depth, move_nps) = (None, None, 0, 0, 0);
+            move_pv = Vec::new();
+            recent_output.clear();
+
+            let bestmove_future = async {
+                let mut exit_seen = false;
+                let mut go_acknowledged = false;
+                let (mut lag_events, mut lagged_lines) = (0u32, 0u64);
+                let mut stop_sent_at: Option<Instant> = None;
+                let mut time_up_sent = false;
+                loop {
+                     // `go infinite` searches until told its time is up
+                     if go_style == GoStyle::InfiniteStop && !time_up_sent && stop_sent_at.is_none() && start.elapsed().as_millis() as i64 >= allotted_ms {
+                         let _ = searching_engine.send("stop".to_string()).await;
+                         time_up_sent = true;
+                     }
+                     // Pausing stops the search rather than letting the engine think on its clock
+                     if stop_sent_at.is_none() && !time_up_sent && *is_paused.lock().await {
+                         let _ = searching_engine.send("stop".to_string()).await;
+                         stop_sent_at = Some(Instant::now());
+                     }
+                     let received = match timeout(Duration::from_millis(ENGINE_EXIT_POLL_MS), active_rx.recv()).await {
+                         Ok(received) => received,
+                         Err(_) => {
+                             // The broadcast stays open while we hold the engine, so a dead process
+                             // only shows up here; one more idle poll lets its last lines drain first.
+                             if !*engine_alive.lock().await {
+                                 if exit_seen {
+                                     return Err(anyhow::anyhow!("Engine process exited"));
+                                 }
+                                 exit_seen = true;
+                             }
+                             continue;
+                         }
+                     };
+                     match received {
+                         Ok(line) => {
+                            if recent_output.len() == STALL_LOG_LINES {
+                                recent_output.pop_front();
+                            }
+                            recent_output.push_back(line.clone());
+                            if !go_acknowledged {
+                                if line == "readyok" {
+                                    go_acknowledged = true;
+                                } else if line.starts_with("bestmove") {
+                                    return Err(ProtocolViolation(format!("sent '{}' before its go command", line)).into());
                                 }
+                                continue;
                             }
-                        }
-                        if line.starts_with("bestmove") {
-                            let parts: Vec<&str> = line.split_whitespace().collect();
-                            if parts.len() > 1 {
-                                let mv = parts[1];
-                                if mv != "(none)" {
-                                    best_move_str = mv.to_string();
+                            if line.starts_with("info") {
+                                if let Some(stats) = parse_info(&line, 0) {
+                                    move_nodes = move_nodes.max(stats.nodes);
+                                    move_depth = move_depth.max(stats.depth);
+                                    move_nps = move_nps.max(stats.nps);
+                                    if !stats.pv.is_empty() {
+                                        move_pv = stats.pv.split_whitespace().take(pv_plies).map(str::to_string).collect();
+                                    }
+                                    if let Some(cp) = stats.score_cp {
+                                         move_score = Some(cp);
+                                         move_mate = None;
+                                    } else if let Some(mate) = stats.score_mate {
+                                         move_mate = Some(mate);
+                                         move_score = None;
+                                    }
                                 }
                             }
-                            return Ok(());
-                        }
-                     },
-                     Err(broadcast::error::RecvError::Lagged(count)) => {
-                         println!("WARNING: Engine broadcast lagged, skipped {} messages. Potential lost bestmove.", count);
-                         continue;
-                     },
-                     Err(broadcast::error::RecvError::Closed) => {
-                         return Err(anyhow::anyhow!("Engine disconnected"));
+                            if line.starts_with("bestmove") {
+                                if let Some(stopped) = stop_sent_at {
+                                    return Err(SearchPaused(stopped.duration_since(start)).into());
+                                }
+                                let parts: Vec<&str> = line.split_whitespace().collect();
+                                if parts.len() > 1 {
+                                    let mv = parts[1];
+                                    if mv != "(none)" {
+                                        best_move_str = mv.to_string();
+                                    }
+                                }
+                                return Ok(());
+                            }
+                         },
+                         Err(broadcast::error::RecvError::Lagged(count)) => {
+                             lag_events += 1;
+                             lagged_lines += count;
+                             searching_engine.record_lag(count);
+                             warn!("Game {}: {:?} engine output lagged {} time(s) this move, {} lines skipped. Potential lost bestmove.", game_id, turn, lag_events, lagged_lines);
+                             // The readyok may be among the skipped lines
+                             go_acknowledged = true;
+                             continue;
+                         },
+                         Err(broadcast::error::RecvError::Closed) => {
+                             return Err(anyhow::anyhow!("Engine disconnected"));
+                         }
                      }
-                 }
+                }
+            };
+
+            // Clocks only tick between moves for wall-clock time controls
+            let ticker = (movetime.is_none() && nodestime.is_none())
+                .then(|| spawn_clock_ticker(time_update_tx.clone(), game_id, turn, white_time, black_time, free_time(inc, kind), start));
+            let result = match sent {
+                Ok(()) => timeout(timeout_duration, bestmove_future).await,
+                Err(e) => {
+                    drop(bestmove_future);
+                    Ok(Err(e))
+                }
+            };
+            if let Some(ticker) = ticker {
+                ticker.abort();
+            }
+            // An aborted game's engines were killed on purpose; that is not a forfeit
+            if *abort_flag.lock().await {
+                return Err(anyhow::anyhow!("aborted"));
+            }
+            // Stopping quits every engine; that is not a crash either
+            if *should_stop.lock().await {
+                return Err(anyhow::anyhow!("stopped"));
             }
+
+            let restarts = match turn { Color::White => &mut white_restarts, Color::Black => &mut black_restarts };
+            if let Ok(Err(crash)) = &result {
+                if *restarts < max_restarts && crash.downcast_ref::<ProtocolViolation>().is_none() && crash.downcast_ref::<SearchPaused>().is_none() {
+                    *restarts += 1;
+                    let (engine_idx, opponent_idx) = match turn { Color::White => (white_idx, black_idx), Color::Black => (black_idx, white_idx) };
+                    let spent = unfinished_search_cost(start.elapsed(), move_nodes, nodestime, movetime);
+                    let _ = active_engine.kill().await;
+                    match turn {
+                        Color::White => diagnostics.white_lagged_lines += active_engine.lagged_lines(),
+                        Color::Black => diagnostics.black_lagged_lines += active_engine.lagged_lines(),
+                    }
+                    let note = format!("{:?} restarted at ply {} ({} of {}): {}", turn, moves_history.len() + 1, restarts, max_restarts, crash);
+                    warn!("Game {}: {}", game_id, note);
+                    diagnostics.engine_restarts.push(note);
+                    match restart_engine(config, engine_idx, opponent_idx, game_id, active_engines, active_games).await {
+                        Ok(engine) => {
+                            // The crashed search's thinking stays on the clock; the move is asked again from scratch
+                            match turn {
+                                Color::White => { white_engine = engine; white_time = (white_time - spent).max(0); }
+                                Color::Black => { black_engine = engine; black_time = (black_time - spent).max(0); }
+                            }
+                            continue 'moves;
+                        }
+                        Err(err) => break (Ok(Err(err)), start),
+                    }
+                }
+            }
+            break (result, start);
         };
 
-        match timeout(timeout_duration, bestmove_future).await {
+        // Charge only the thinking done before the pause; the search restarts with a fresh `go` on resume
+        if let Ok(Err(e)) = &bestmove_result {
+            if let Some(SearchPaused(thinking)) = e.downcast_ref::<SearchPaused>() {
+                let spent = unfinished_search_cost(*thinking, move_nodes, nodestime, movetime);
+                match turn {
+                    Color::White => white_time = (white_time - spent).max(0),
+                    Color::Black => black_time = (black_time - spent).max(0),
+                }
+                info!("Game {}: {:?} stopped for a pause after {} ms", game_id, turn, thinking.as_millis());
+                continue;
+            }
+        }
+
+        match bestmove_result {
             Ok(Ok(_)) => {},
             Ok(Err(e)) => {
                  // Engine disconnected/closed
-                 println!("Engine error: {}", e);
+                 error!("Game {}: {:?} engine error: {}", game_id, turn, e);
                  game_result = match turn { Color::White => "0-1", Color::Black => "1-0" }.to_string();
+                 let reason = if e.downcast_ref::<ProtocolViolation>().is_some() { TerminationReason::ProtocolViolation } else { TerminationReason::EngineDisconnected };
+                 diagnostics.terminate(reason, format!("{:?} to move: {}", turn, e));
                  let _ = game_update_tx.send(GameUpdate {
-                    fen: pos.to_fen_string(), last_move: None, white_time: white_time as u64, black_time: black_time as u64,
+                    fen: pos.to_fen_string(), last_move: None, pv_moves: None, white_time: white_time as u64, black_time: black_time as u64,
                     move_number: current_move_num as u32, result: Some(game_result.clone()), white_engine_idx: white_idx, black_engine_idx: black_idx,
-                    game_id
+                    game_id, termination: termination_tag(&diagnostics), end_reason: end_reason(&diagnostics), start_fen: None
                 }).await;
                 break;
             },
             Err(_) => {
-                 // Timed out
-                 println!("Engine timed out!");
-                 let _ = active_engine.kill().await;
+                 // Timed out: nothing moved on the board for the whole allowance
+                 let last_output = if recent_output.is_empty() { "(none)".to_string() } else { Vec::from(recent_output.clone()).join(" | ") };
+                 warn!("Game {}: {:?} stalled for {} ms; last engine output: {}", game_id, turn, timeout_duration.as_millis(), last_output);
+                 let _ = match turn { Color::White => &white_engine, Color::Black => &black_engine }.kill().await;
                  game_result = match turn { Color::White => "0-1", Color::Black => "1-0" }.to_string();
+                 diagnostics.terminate(TerminationReason::TimeForfeit, format!("{:?} sent no bestmove within {} ms ({} ms on the clock); last output: {}", turn, timeout_duration.as_millis(), time_left, last_output));
                  let _ = game_update_tx.send(GameUpdate {
-                    fen: pos.to_fen_string(), last_move: None, white_time: white_time as u64, black_time: black_time as u64,
+                    fen: pos.to_fen_string(), last_move: None, pv_moves: None, white_time: white_time as u64, black_time: black_time as u64,
+                    move_number: current_move_num as u32, result: Some(game_result.clone()), white_engine_idx: white_idx, black_engine_idx: black_idx,
+                    game_id, termination: termination_tag(&diagnostics), end_reason: end_reason(&diagnostics), start_fen: None
+                }).await;
+                break;
+            }
+        }
+
+        let pv_arrows = (!move_pv.is_empty()).then(|| move_pv.clone());
+
+        let elapsed = match nodestime {
+            Some(nodes_per_ms) => (move_nodes / nodes_per_ms) as i64,
+            None => (start.elapsed().as_millis() as i64 - move_overhead).max(0),
+        };
+        if movetime.is_none() {
+            match turn {
+                Color::White => white_time = charge_clock(white_time, elapsed, white_inc, white_kind),
+                Color::Black => black_time = charge_clock(black_time, elapsed, black_inc, black_kind),
+            }
+        }
+        let budget = soft_move_budget(time_left, inc, movetime);
+        let search = match turn { Color::White => &mut white_search, Color::Black => &mut black_search };
+        search.record_move(move_depth, move_nodes, move_nps);
+        if elapsed > budget * OVERSHOOT_FACTOR {
+            search.overshot_moves += 1;
+        }
+        diagnostics.record_move(MoveDiagnostics {
+            ply: moves_history.len() + 1,
+            side: format!("{:?}", turn),
+            bestmove: best_move_str.clone(),
+            score_cp: move_score,
+            score_mate: move_mate,
+            depth: move_depth,
+            nodes: move_nodes,
+            time_ms: elapsed,
+            budget_ms: budget,
+            overshoot_ms: elapsed - budget,
+            white_clock_ms: white_time,
+            black_clock_ms: black_time,
+            halfmove_clock,
+            ..Default::default()
+        });
+
+        // Mate Adjudication (kept apart from centipawn resign so mate scores don't count as huge evals)
+        if !adjudication.disable_score_adjudication {
+            let mate_for_white = move_mate.map(|mate| white_relative(mate, turn));
+            match turn {
+                Color::White => white_mate = mate_for_white,
+                Color::Black => black_mate = mate_for_white,
+            }
+            let mate_count_limit = adjudication.mate_move_count.unwrap_or(5);
+            mate_streak.record(mate_for_white);
+            if move_mate.is_some() {
+                consec_draw_moves = 0;
+            }
+
+            // Both engines agree on who gets mated: no need to play it out.
+            let agreed_winner = match (white_mate, black_mate) {
+                (Some(w), Some(b)) if w > 0 && b > 0 => Some(Color::White),
+                (Some(w), Some(b)) if w < 0 && b < 0 => Some(Color::Black),
+                _ => None,
+            };
+            let mate_winner = agreed_winner
+                .or_else(|| (mate_streak.moves >= mate_count_limit).then(|| mate_streak.winner()));
+
+            diagnostics.set_counters(resign_streak.moves, consec_draw_moves, mate_streak.moves);
+            if let Some(winner) = mate_winner {
+                game_result = match winner { Color::White => "1-0", Color::Black => "0-1" }.to_string();
+                let detail = if agreed_winner.is_some() {
+                    format!("both engines report mate (White's view): white {:?}, black {:?}", white_mate, black_mate)
+                } else {
+                    format!("mate {:?} reported for {} consecutive moves (limit {})", move_mate, mate_streak.moves, mate_count_limit)
+                };
+                diagnostics.terminate(TerminationReason::MateAdjudication, detail);
+                let _ = game_update_tx.send(GameUpdate {
+                    fen: pos.to_fen_string(), last_move: Some(best_move_str.clone()), pv_moves: pv_arrows.clone(), white_time: white_time as u64, black_time: black_time as u64,
                     move_number: current_move_num as u32, result: Some(game_result.clone()), white_engine_idx: white_idx, black_engine_idx: black_idx,
-                    game_id
+                    game_id, termination: termination_tag(&diagnostics), end_reason: end_reason(&diagnostics), start_fen: None
                 }).await;
                 break;
             }
         }
 
-        let elapsed = start.elapsed().as_millis() as i64;
+        let eval_for_white = move_score.map(|score| white_relative(score, turn));
         match turn {
-            Color::White => white_time = (white_time - elapsed).max(0) + inc,
-            Color::Black => black_time = (black_time - elapsed).max(0) + inc,
+            Color::White => white_eval = eval_for_white,
+            Color::Black => black_eval = eval_for_white,
         }
 
         // Adjudication Checks
-        if let Some(score) = move_score {
+        if let Some(score) = move_score.filter(|_| !adjudication.disable_score_adjudication) {
              // Resign Adjudication
-             let resign_threshold = config.adjudication.resign_score.unwrap_or(1000);
-             let resign_count_limit = config.adjudication.resign_move_count.unwrap_or(5);
+             let resign_threshold = adjudication.resign_score.unwrap_or(1000);
+             let resign_count_limit = adjudication.resign_move_count.unwrap_or(5);
 
-             if score.abs() >= resign_threshold {
-                 consec_resign_moves += 1;
-             } else {
-                 consec_resign_moves = 0;
-             }
+             resign_streak.record(eval_for_white.filter(|_| score.abs() >= resign_threshold));
 
              // Draw Adjudication
-             let draw_threshold = config.adjudication.draw_score.unwrap_or(5); // +/- cp
-             let draw_start = config.adjudication.draw_move_number.unwrap_or(40);
-             let draw_count_limit = config.adjudication.draw_move_count.unwrap_or(20);
+             let draw_threshold = adjudication.draw_score.unwrap_or(5); // +/- cp
+             let draw_start = adjudication.draw_move_number.unwrap_or(40);
+             let draw_count_limit = adjudication.draw_move_count.unwrap_or(20);
 
              if current_move_num as u32 >= draw_start {
                  if score.abs() <= draw_threshold {
@@ -1190,27 +3339,38 @@ async fn play_game_static(
                  consec_draw_moves = 0;
              }
 
-             if consec_resign_moves >= resign_count_limit {
-                 let result_str = if score > 0 {
-                     match turn { Color::White => "1-0", Color::Black => "0-1" }
+             diagnostics.set_counters(resign_streak.moves, consec_draw_moves, mate_streak.moves);
+             let agreed_winner = if adjudication.resign_immediate_on_agreement {
+                 resign_agreement(white_eval, black_eval, resign_threshold)
+             } else {
+                 None
+             };
+             let resign_winner = agreed_winner
+                 .or_else(|| (resign_streak.moves >= resign_count_limit).then(|| resign_streak.winner()));
+             if let Some(winner) = resign_winner {
+                 let detail = if agreed_winner.is_none() {
+                     format!("score {} cp, |score| >= {} for the same side for {} consecutive moves (limit {})", score, resign_threshold, resign_streak.moves, resign_count_limit)
                  } else {
-                     match turn { Color::White => "0-1", Color::Black => "1-0" }
+                     format!("both engines past {} cp (White's view): white {:?}, black {:?}", resign_threshold, white_eval, black_eval)
                  };
+                 diagnostics.terminate(TerminationReason::ResignAdjudication, detail);
+                 let result_str = match winner { Color::White => "1-0", Color::Black => "0-1" };
                  game_result = result_str.to_string();
                  let _ = game_update_tx.send(GameUpdate {
-                    fen: pos.to_fen_string(), last_move: Some(best_move_str.clone()), white_time: white_time as u64, black_time: black_time as u64,
+                    fen: pos.to_fen_string(), last_move: Some(best_move_str.clone()), pv_moves: pv_arrows.clone(), white_time: white_time as u64, black_time: black_time as u64,
                     move_number: current_move_num as u32, result: Some(result_str.to_string()), white_engine_idx: white_idx, black_engine_idx: black_idx,
-                    game_id
+                    game_id, termination: termination_tag(&diagnostics), end_reason: end_reason(&diagnostics), start_fen: None
                 }).await;
                 break;
              }
 
              if consec_draw_moves >= draw_count_limit {
                  game_result = "1/2-1/2".to_string();
+                 diagnostics.terminate(TerminationReason::DrawAdjudication, format!("score {} cp, |score| <= {} for {} consecutive moves from move {} (limit {})", score, draw_threshold, consec_draw_moves, draw_start, draw_count_limit));
                  let _ = game_update_tx.send(GameUpdate {
-                    fen: pos.to_fen_string(), last_move: Some(best_move_str.clone()), white_time: white_time as u64, black_time: black_time as u64,
+                    fen: pos.to_fen_string(), last_move: Some(best_move_str.clone()), pv_moves: pv_arrows.clone(), white_time: white_time as u64, black_time: black_time as u64,
                     move_number: current_move_num as u32, result: Some("1/2-1/2".to_string()), white_engine_idx: white_idx, black_engine_idx: black_idx,
-                    game_id
+                    game_id, termination: termination_tag(&diagnostics), end_reason: end_reason(&diagnostics), start_fen: None
                 }).await;
                 break;
              }
@@ -1231,84 +3391,272 @@ async fn play_game_static(
             }
 
             let repetition_count = repetition_counts
-                .entry(repetition_key(&pos.to_fen_string()))
+                .entry(pos.repetition_key())
                 .and_modify(|count| *count += 1)
                 .or_insert(1);
 
-            if *repetition_count >= 3 || halfmove_clock >= 100 {
+            // 3-fold and the 75-move rule end the game automatically; the 50-move rule only if claimed
+            let fifty_move_claim = adjudication.claim_fifty_move && halfmove_clock >= 100;
+            if *repetition_count >= 3 || halfmove_clock >= 150 || fifty_move_claim {
                 game_result = "1/2-1/2".to_string();
+                let reason = if *repetition_count >= 3 {
+                    TerminationReason::ThreefoldRepetition
+                } else if halfmove_clock >= 150 {
+                    TerminationReason::SeventyFiveMoveRule
+                } else {
+                    TerminationReason::FiftyMoveRule
+                };
+                diagnostics.terminate(reason, format!("repetitions {}, halfmove clock {}", repetition_count, halfmove_clock));
                 let _ = game_update_tx.send(GameUpdate {
-                    fen: pos.to_fen_string(), last_move: Some(best_move_str.clone()), white_time: white_time as u64, black_time: black_time as u64,
+                    fen: pos.to_fen_string(), last_move: Some(best_move_str.clone()), pv_moves: pv_arrows.clone(), white_time: white_time as u64, black_time: black_time as u64,
                     move_number: current_move_num as u32, result: Some(game_result.clone()), white_engine_idx: white_idx, black_engine_idx: black_idx,
-                    game_id
+                    game_id, termination: termination_tag(&diagnostics), end_reason: end_reason(&diagnostics), start_fen: None
                 }).await;
                 break;
             }
         } else {
-             println!("Illegal/Unparseable move from {}: {}", if turn == Color::White { "White" } else { "Black" }, best_move_str);
+             if regenerable_start && moves_history.is_empty() {
+                 // The very first reply refused the generated setup; let the caller pick another one.
+                 return Err(StartPositionRejected.into());
+             }
+             // Legal moves exist here (the game isn't over), so a null bestmove breaks the protocol
+             if is_null_bestmove(&best_move_str) {
+                 error!("Game {}: null bestmove from {:?} with legal moves available: {:?}", game_id, turn, best_move_str);
+                 diagnostics.terminate(TerminationReason::ProtocolViolation, format!("{:?} sent a null bestmove in {}, which has legal moves", turn, pos.to_fen_string()));
+             } else {
+                 error!("Game {}: illegal or unparseable move from {:?}: {}", game_id, turn, best_move_str);
+                 diagnostics.terminate(TerminationReason::IllegalMove, format!("{:?} played {:?} in {}", turn, best_move_str, pos.to_fen_string()));
+             }
              // Forfeit the engine that made the illegal move
              game_result = match turn {
                  Color::White => "0-1",
                  Color::Black => "1-0",
              }.to_string();
              let _ = game_update_tx.send(GameUpdate {
-                fen: pos.to_fen_string(), last_move: Some(best_move_str.clone()), white_time: white_time as u64, black_time: black_time as u64,
+                fen: pos.to_fen_string(), last_move: Some(best_move_str.clone()), pv_moves: pv_arrows.clone(), white_time: white_time as u64, black_time: black_time as u64,
                 move_number: current_move_num as u32, result: Some(game_result.clone()), white_engine_idx: white_idx, black_engine_idx: black_idx,
-                game_id
+                game_id, termination: termination_tag(&diagnostics), end_reason: end_reason(&diagnostics), start_fen: None
             }).await;
              break;
         }
 
         let _ = game_update_tx.send(GameUpdate {
-            fen: pos.to_fen_string(), last_move: Some(best_move_str), white_time: white_time as u64, black_time: black_time as u64,
+            fen: pos.to_fen_string(), last_move: Some(best_move_str), pv_moves: pv_arrows, white_time: white_time as u64, black_time: black_time as u64,
             move_number: (current_move_num + 1) as u32, result: None, white_engine_idx: white_idx, black_engine_idx: black_idx,
-            game_id
+            game_id, termination: None, end_reason: None, start_fen: None
         }).await;
+
+        if let Some(live_tx) = live_pgn_tx {
+            let pgn = format_pgn(&PgnGame {
+                moves: &moves_history,
+                result: "*",
+                white: &config.engines[white_idx].name,
+                black: &config.engines[black_idx].name,
+                start_fen,
+                round: pgn_round(config, game_id),
+                termination: None,
+                final_comment: None,
+            }, &PgnHeader::from_config(config));
+            let _ = live_tx.send(LivePgnUpdate::Moves { game_id, pgn }).await;
+        }
+    }
+    diagnostics.result = Some(game_result.clone());
+    match &diagnostics.termination {
+        Some(termination) => info!("Game {}: {} by {} ({})", game_id, game_result, termination.reason, termination.detail),
+        None => info!("Game {}: {}", game_id, game_result),
+    }
+    diagnostics.white_lagged_lines += white_engine.lagged_lines();
+    diagnostics.black_lagged_lines += black_engine.lagged_lines();
+    Ok(PlayedGame { result: game_result, moves: moves_history, white_search, black_search, white_restarts, black_restarts, diagnostics })
+}
+
+/// PGN `[Round]` of a game: its id, shifted past earlier batches by `round_offset`.
+fn pgn_round(config: &TournamentConfig, game_id: usize) -> usize {
+    game_id + config.round_offset.unwrap_or(0)
+}
+
+/// Highest numeric `[Round]` tag in the PGN at `path`, 0 when there is none.
+fn last_pgn_round(path: &str) -> usize {
+    let Ok(data) = std::fs::read_to_string(path) else { return 0; };
+    data.lines()
+        .filter_map(|line| line.trim().strip_prefix("[Round \""))
+        .filter_map(|round| {
+            let digits: String = round.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse::<usize>().ok()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+fn load_opening_pins(path: &str) -> anyhow::Result<HashMap<usize, String>> {
+    let data = std::fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to open opening pins file: {}", e))?;
+    let pins = serde_json::from_str(&data).map_err(|e| anyhow::anyhow!("Invalid opening pins file: {}", e))?;
+    Ok(pins)
+}
+
+/// Splits an opening into its FEN and the UCI moves played from it, written like
+/// the `position` command: `<fen> moves e2e4 e7e5`.
+fn split_opening(opening: &str) -> (&str, Vec<&str>) {
+    match opening.split_once(" moves ") {
+        Some((fen, moves)) => (fen.trim(), moves.split_whitespace().collect()),
+        None => (opening.trim(), Vec::new()),
+    }
+}
+
+fn is_valid_start_fen(fen: &str, variant: &str) -> bool {
+    let mode = if variant == "chess960" { CastlingMode::Chess960 } else { CastlingMode::Standard };
+    let (fen, moves) = split_opening(fen);
+    let Some(mut pos) = Fen::from_ascii(fen.as_bytes())
+        .ok()
+        .and_then(|setup| setup.into_position::<Chess>(mode).ok()) else { return false };
+    moves.iter().all(|mv| match mv.parse::<Uci>().ok().and_then(|uci| uci.to_move(&pos).ok()) {
+        Some(m) => { pos.play_unchecked(&m); true }
+        None => false,
+    })
+}
+
+/// One PGN game as an opening: its `[FEN]` (or the standard start) plus the first
+/// `depth` plies of its mainline, or `None` when a move doesn't parse or isn't legal.
+fn pgn_opening_line(fen: Option<&str>, movetext: &str, variant: &str, depth: Option<u32>) -> Option<String> {
+    let mode = if variant == "chess960" { CastlingMode::Chess960 } else { CastlingMode::Standard };
+    let fen = fen.unwrap_or(STANDARD_START_FEN);
+    let mut pos: Chess = Fen::from_ascii(fen.as_bytes()).ok()?.into_position(mode).ok()?;
+
+    // Comments, variations and NAGs aren't part of the mainline
+    let mut mainline = String::new();
+    let (mut comment, mut variation_depth) = (false, 0);
+    for c in movetext.chars() {
+        match c {
+            '{' if !comment => comment = true,
+            '}' if comment => comment = false,
+            '(' if !comment => variation_depth += 1,
+            ')' if !comment && variation_depth > 0 => variation_depth -= 1,
+            _ if comment || variation_depth > 0 => {}
+            _ => mainline.push(c),
+        }
+    }
+
+    let mut moves = Vec::new();
+    for token in mainline.split_whitespace() {
+        if depth.is_some_and(|depth| moves.len() >= depth as usize) {
+            break;
+        }
+        if matches!(token, "1-0" | "0-1" | "1/2-1/2" | "*") || token.starts_with('$') {
+            continue;
+        }
+        let san = token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.').trim_end_matches(['!', '?']);
+        if san.is_empty() {
+            continue;
+        }
+        let m = SanPlus::from_ascii(san.as_bytes()).ok()?.san.to_move(&pos).ok()?;
+        moves.push(m.to_uci(mode).to_string());
+        pos.play_unchecked(&m);
+    }
+    if moves.is_empty() {
+        Some(fen.to_string())
+    } else {
+        Some(format!("{} moves {}", fen, moves.join(" ")))
     }
-    Ok((game_result, moves_history))
 }
 
-fn load_openings(path: &str) -> anyhow::Result<Vec<String>> {
+/// The openings in `path` and how many of its lines weren't valid openings.
+fn load_openings(path: &str, variant: &str, depth: Option<u32>) -> anyhow::Result<(Vec<String>, usize)> {
     let file = std::fs::File::open(path).map_err(|e| anyhow::anyhow!("Failed to open opening file: {}", e))?;
     let reader = std::io::BufReader::new(file);
     let mut fens = Vec::new();
+    let mut skipped = 0;
     let is_pgn = path.ends_with(".pgn");
+    // Current PGN game: its [FEN] tag and movetext
+    let mut tag_fen: Option<String> = None;
+    let mut movetext = String::new();
+    let finish_game = |tag_fen: &mut Option<String>, movetext: &mut String, fens: &mut Vec<String>, skipped: &mut usize| {
+        let fen = tag_fen.take();
+        let text = std::mem::take(movetext);
+        match pgn_opening_line(fen.as_deref(), &text, variant, depth) {
+            // A game with neither a FEN nor moves has no opening to offer
+            Some(line) if fen.is_none() && line == STANDARD_START_FEN => {}
+            Some(line) if is_valid_start_fen(&line, variant) => fens.push(line),
+            _ => *skipped += 1,
+        }
+    };
 
     for line_res in reader.lines() {
         let line = line_res?;
         let line = line.trim();
         if line.is_empty() { continue; }
         if is_pgn {
-            // Simple PGN FEN extraction
-            if line.starts_with("[FEN \"") && line.ends_with("\"]") {
-                let fen = &line[6..line.len()-2];
-                fens.push(fen.to_string());
+            if line.starts_with('[') {
+                let fen_tag = (line.starts_with("[FEN \"") && line.ends_with("\"]")).then(|| &line[6..line.len()-2]);
+                // Tags after movetext, or a second FEN, start the next game
+                if !movetext.trim().is_empty() || (fen_tag.is_some() && tag_fen.is_some()) {
+                    finish_game(&mut tag_fen, &mut movetext, &mut fens, &mut skipped);
+                }
+                if let Some(fen) = fen_tag {
+                    tag_fen = Some(fen.to_string());
+                }
+            } else {
+                let text = line.split(';').next().unwrap_or("");
+                movetext.push_str(text);
+                movetext.push(' ');
             }
         } else {
             // Assume EPD: take everything before first " ;" or just the whole line if clean
             let parts: Vec<&str> = line.split(';').collect();
-            fens.push(parts[0].trim().to_string());
+            let fen = parts[0].trim();
+            // EPD lines may carry opcodes after the four board fields
+            let epd_fen = fen.split_whitespace().take(4).collect::<Vec<_>>().join(" ");
+            if is_valid_start_fen(fen, variant) {
+                fens.push(fen.to_string());
+            } else if is_valid_start_fen(&epd_fen, variant) {
+                fens.push(epd_fen);
+            } else {
+                skipped += 1;
+            }
         }
     }
+    if is_pgn && (tag_fen.is_some() || !movetext.trim().is_empty()) {
+        finish_game(&mut tag_fen, &mut movetext, &mut fens, &mut skipped);
+    }
     if fens.is_empty() {
         return Err(anyhow::anyhow!("No valid openings found in file"));
     }
-    Ok(fens)
+    Ok((fens, skipped))
+}
+
+/// The free text of an `info string` line; per UCI it runs to the end of the line.
+pub(crate) fn info_string(line: &str) -> Option<&str> {
+    let mut rest = line.strip_prefix("info")?;
+    loop {
+        rest = rest.trim_start();
+        let token = rest.split_whitespace().next()?;
+        rest = &rest[token.len()..];
+        if token == "string" {
+            return Some(rest.trim());
+        }
+    }
 }
 
-fn parse_info(line: &str, engine_idx: usize) -> Option<EngineStats> {
-    let mut depth = 0;
+/// `None` for lines that are only an `info string`, which carry no search stats.
+pub(crate) fn parse_info(line: &str, engine_idx: usize) -> Option<EngineStats> {
+    if line.split_whitespace().nth(1) == Some("string") {
+        return None;
+    }
+    let mut depth = None;
     let mut nodes = 0;
     let mut score_cp = None;
     let mut score_mate = None;
     let mut pv = String::new();
     let mut nps = 0;
+    let mut tb_hits = None;
+    let mut hash_full = None;
+    let mut curr_move = None;
+    let mut curr_move_number = None;
     let mut iter = line.split_whitespace().peekable();
     while let Some(token) = iter.next() {
         match token {
             "depth" => {
                 if let Some(value) = iter.next() {
-                    depth = value.parse().unwrap_or(0);
+                    depth = Some(value.parse().unwrap_or(0));
                 }
             }
             "nodes" => {
@@ -1321,6 +3669,18 @@ fn parse_info(line: &str, engine_idx: usize) -> Option<EngineStats> {
                     nps = value.parse().unwrap_or(0);
                 }
             }
+            "tbhits" => {
+                tb_hits = iter.next().and_then(|value| value.parse().ok());
+            }
+            "hashfull" => {
+                hash_full = iter.next().and_then(|value| value.parse().ok());
+            }
+            "currmove" => {
+                curr_move = iter.next().map(str::to_string);
+            }
+            "currmovenumber" => {
+                curr_move_number = iter.next().and_then(|value| value.parse().ok());
+            }
             "score" => {
                 let kind = iter.next();
                 let value = iter.next();
@@ -1342,10 +3702,17 @@ fn parse_info(line: &str, engine_idx: usize) -> Option<EngineStats> {
                 pv = moves.join(" ");
                 break;
             }
+            // Free text to the end of the line, which could hold any of the tokens above
+            "string" => break,
             _ => {}
         }
     }
-    Some(EngineStats { depth, score_cp, score_mate, nodes, nps, pv, engine_idx, game_id: 0, tb_hits: None, hash_full: None }) // Placeholder 0, will be overwritten or context aware
+    // A bare progress line like `info currmove e2e4 currmovenumber 3` isn't a search result
+    if depth.is_none() && curr_move.is_some() {
+        return None;
+    }
+    let depth = depth.unwrap_or(0);
+    Some(EngineStats { depth, score_cp, score_mate, nodes, nps, pv, engine_idx, game_id: 0, tb_hits, hash_full, curr_move, curr_move_number }) // Placeholder 0, will be overwritten or context aware
 }
 
 fn parse_info_with_id(line: &str, engine_idx: usize, game_id: usize) -> Option<EngineStats> {
@@ -1353,3 +3720,883 @@ fn parse_info_with_id(line: &str, engine_idx: usize, game_id: usize) -> Option<E
     stats.game_id = game_id;
     Some(stats)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A moveless A-B game from the standard position.
+    fn empty_game(result: &str, round: usize) -> PgnGame<'_> {
+        PgnGame { moves: &[], result, white: "A", black: "B", start_fen: STANDARD_START_FEN, round, termination: None, final_comment: None }
+    }
+
+    /// A standard-chess config with a 1s clock, no book and every engine at `/bin/true`;
+    /// tests override the fields they care about.
+    fn test_config(mode: TournamentMode, engines: &[&str], games_count: u32) -> TournamentConfig {
+        let engines: Vec<_> = engines.iter()
+            .map(|name| serde_json::json!({ "name": name, "path": "/bin/true", "options": [] }))
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "mode": mode,
+            "engines": engines,
+            "time_control": { "base_ms": 1000, "inc_ms": 0 },
+            "games_count": games_count,
+            "swap_sides": true,
+            "opening": {},
+            "variant": "standard",
+            "disabled_engine_ids": [],
+            "adjudication": { "result_adjudication": false },
+        })).unwrap()
+    }
+
+    #[test]
+    fn parse_info_reads_all_tokens() {
+        let line = "info depth 21 seldepth 30 score cp 35 nodes 1500000 nps 750000 tbhits 42 hashfull 512 pv e2e4 e7e5";
+        let stats = parse_info(line, 1).unwrap();
+        assert_eq!(stats.depth, 21);
+        assert_eq!(stats.score_cp, Some(35));
+        assert_eq!(stats.nodes, 1_500_000);
+        assert_eq!(stats.nps, 750_000);
+        assert_eq!(stats.tb_hits, Some(42));
+        assert_eq!(stats.hash_full, Some(512));
+        assert_eq!(stats.pv, "e2e4 e7e5");
+    }
+
+    #[test]
+    fn info_string_text_is_kept_out_of_the_stats() {
+        assert_eq!(info_string("info string NNUE evaluation using nn.bin pv depth"), Some("NNUE evaluation using nn.bin pv depth"));
+        assert!(parse_info("info string NNUE evaluation using nn.bin pv depth", 0).is_none());
+        // Stats before the string still count, nothing after it does
+        let stats = parse_info("info depth 12 nodes 5000 string depth 99 pv a2a3", 0).unwrap();
+        assert_eq!((stats.depth, stats.nodes, stats.pv.as_str()), (12, 5000, ""));
+        assert_eq!(info_string("info depth 12 nodes 5000 string depth 99 pv a2a3"), Some("depth 99 pv a2a3"));
+        assert_eq!(info_string("info depth 12 pv e2e4"), None);
+        assert_eq!(engine_log_path(Path::new("logs"), "Stockfish 16/nnue"), Path::new("logs").join("Stockfish_16_nnue.log"));
+    }
+
+    #[test]
+    fn parse_info_keeps_currmove_only_lines() {
+        let stats = parse_info("info depth 18 currmove g1f3 currmovenumber 12", 0).unwrap();
+        assert_eq!(stats.curr_move.as_deref(), Some("g1f3"));
+        assert_eq!(stats.curr_move_number, Some(12));
+        assert_eq!(stats.depth, 18);
+        assert_eq!((stats.score_cp, stats.score_mate, stats.pv.as_str()), (None, None, ""));
+        // Without a depth there's nothing to report, rather than zeroed stats
+        assert!(parse_info("info currmove g1f3 currmovenumber 12", 0).is_none());
+    }
+
+    #[test]
+    fn load_openings_skips_invalid_fens() {
+        let path = std::env::temp_dir().join(format!("openings_{}.epd", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1\nnot a fen at all\n").unwrap();
+        let (openings, skipped) = load_openings(&path.to_string_lossy(), "standard", None).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(openings, vec!["rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1".to_string()]);
+        assert_eq!(skipped, 1);
+    }
+
+    #[test]
+    fn pgn_opening_lines_stop_at_the_book_depth() {
+        let path = std::env::temp_dir().join(format!("openings_{}.pgn", uuid::Uuid::new_v4()));
+        let pgn = "[Event \"Book\"]\n\n1. e4 {King's pawn} e5 2. Nf3 (2. f4 exf4) Nc6 3. Bb5 a6 *\n\n\
+                   [Event \"Book\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 w - - 0 1\"]\n\n1. e4 Kd7 2. Qh5 *\n\n\
+                   [FEN \"8/8/8/8/8/8/8/4K2k w - - 0 1\"]\n";
+        std::fs::write(&path, pgn).unwrap();
+        let (openings, _) = load_openings(&path.to_string_lossy(), "standard", Some(4)).unwrap();
+        let _ = std::fs::remove_file(&path);
+        // The second line has no queen to play Qh5 and is skipped
+        assert_eq!(openings, vec![
+            format!("{} moves e2e4 e7e5 g1f3 b8c6", STANDARD_START_FEN),
+            "8/8/8/8/8/8/8/4K2k w - - 0 1".to_string(),
+        ]);
+        assert!(is_valid_start_fen(&openings[0], "standard"));
+        assert_eq!(split_opening(&openings[0]), (STANDARD_START_FEN, vec!["e2e4", "e7e5", "g1f3", "b8c6"]));
+    }
+
+    #[test]
+    fn pgn_book_games_are_dealt_out_in_turn() {
+        let path = std::env::temp_dir().join(format!("openings_{}.pgn", uuid::Uuid::new_v4()));
+        let pgn = "[Event \"Open\"]\n\n1. e4 e5 *\n\n[Event \"Queen\"]\n\n1. d4 d5 *\n\n[Event \"English\"]\n\n1. c4 c5 *\n";
+        std::fs::write(&path, pgn).unwrap();
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 8);
+        config.opening.file = Some(path.to_string_lossy().to_string());
+        config.opening.order = Some("sequential".to_string());
+        let (openings, _, _) = load_opening_book(&config).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        // Both colors of a pair share a line, and the book wraps around
+        let moves: Vec<String> = (0..8)
+            .map(|game_idx| split_opening(&openings[opening_index(&config, game_idx) % openings.len()]).1.join(" "))
+            .collect();
+        assert_eq!(moves, vec!["e2e4 e7e5", "e2e4 e7e5", "d2d4 d7d5", "d2d4 d7d5", "c2c4 c7c5", "c2c4 c7c5", "e2e4 e7e5", "e2e4 e7e5"]);
+    }
+
+    #[test]
+    fn start_fens_are_shared_by_each_swapped_pair() {
+        let fens = ["4k3/8/8/8/8/8/4P3/4K3 w - - 0 1", "4k3/4p3/8/8/8/8/8/4K3 w - - 0 1"];
+        let config = |start_fens: &[&str]| {
+            let mut config = test_config(TournamentMode::Match, &["A", "B"], 6);
+            config.opening.start_fens = Some(start_fens.iter().map(|fen| fen.to_string()).collect());
+            config.opening.order = Some("random".to_string());
+            config
+        };
+
+        let schedule = Arbiter::preview_schedule(&config(&fens)).unwrap();
+        let games: Vec<(&str, &str)> = schedule.iter()
+            .map(|game| (game.white_name.as_str(), game.start_fen.as_deref().unwrap()))
+            .collect();
+        // Never shuffled, and the list wraps around like a book
+        assert_eq!(games, vec![("A", fens[0]), ("B", fens[0]), ("A", fens[1]), ("B", fens[1]), ("A", fens[0]), ("B", fens[0])]);
+
+        let err = Arbiter::preview_schedule(&config(&[fens[0], "8/8/8/8/8/8/8/8 w - - 0 1"])).unwrap_err();
+        assert!(err.to_string().contains("Invalid start FEN 1"), "{}", err);
+    }
+
+    #[test]
+    fn openings_are_labelled_by_name_when_known() {
+        let sicilian = format!("{} moves e2e4 c7c5", STANDARD_START_FEN);
+        assert_eq!(opening_label(&sicilian), "Sicilian Defence");
+        // Classified by position, so a FEN already past the moves names it too
+        assert_eq!(opening_label("rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2"), "Sicilian Defence");
+        let endgame = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1";
+        assert_eq!(opening_label(endgame), endgame);
+
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 2);
+        config.opening.start_fens = Some(vec![sicilian.to_string(), endgame.to_string()]);
+        let schedule = Arbiter::preview_schedule(&config).unwrap();
+        assert!(schedule.iter().all(|game| game.opening.as_deref() == Some("Sicilian Defence")));
+    }
+
+    #[test]
+    fn opening_seed_reproduces_book_order_and_chess960_positions() {
+        let path = std::env::temp_dir().join(format!("openings_{}.epd", uuid::Uuid::new_v4()));
+        let book: Vec<String> = (1..=8).map(|n| format!("{} {}", &STANDARD_START_FEN[..STANDARD_START_FEN.len() - 2], n)).collect();
+        std::fs::write(&path, book.join("\n")).unwrap();
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 2);
+        config.opening.file = Some(path.to_string_lossy().to_string());
+        config.opening.order = Some("random".to_string());
+        config.opening.seed = Some(7);
+
+        let (first, _, _) = load_opening_book(&config).unwrap();
+        let (second, _, _) = load_opening_book(&config).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(first, second);
+        assert_ne!(first, book);
+
+        let position = |game_id| generate_start_fen("chess960", &mut game_rng(&config, game_id));
+        assert_eq!(position(3), position(3));
+    }
+
+    #[test]
+    fn isolated_engines_get_distinct_working_dirs() {
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 1);
+        assert!(engines_share_files(&config.engines[0], &config.engines[1]));
+        assert_eq!(engine_working_dir(&config, 1, 0, 1), None);
+
+        config.isolate_engine_dirs = true;
+        let dir_a = engine_working_dir(&config, 1, 0, 1).unwrap();
+        let dir_b = engine_working_dir(&config, 1, 1, 0).unwrap();
+        assert_ne!(dir_a, dir_b);
+        assert!(dir_a.starts_with(std::env::temp_dir()));
+    }
+
+    #[test]
+    fn repetition_key_only_counts_legal_en_passant() {
+        let board = |fen: &str| {
+            let setup = Fen::from_ascii(fen.as_bytes()).unwrap();
+            Board::Standard(setup.into_position(CastlingMode::Standard).unwrap())
+        };
+        // exd6 is legal, so the e.p. right makes this a different position
+        assert_ne!(
+            board("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").repetition_key(),
+            board("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1").repetition_key(),
+        );
+        // Nothing can capture on d6, so the square in the FEN is irrelevant
+        assert_eq!(
+            board("4k3/8/8/3p4/8/8/8/4K3 w - d6 0 1").repetition_key(),
+            board("4k3/8/8/3p4/8/8/8/4K3 w - - 0 1").repetition_key(),
+        );
+    }
+
+    #[test]
+    fn auto_concurrency_fits_engine_threads_into_cores() {
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 1);
+        config.engines[0].options = vec![("Threads".to_string(), "2".to_string())];
+        config.engines[1].options = vec![("Threads".to_string(), "1".to_string())];
+        config.concurrency = Some(6);
+        assert_eq!(max_engine_threads(&config), 2);
+        assert_eq!(effective_concurrency(&config), 6);
+
+        config.concurrency = None;
+        let cores = available_cores().unwrap();
+        assert_eq!(effective_concurrency(&config), (cores / 2).max(1));
+    }
+
+    #[tokio::test]
+    async fn appending_to_a_pgn_continues_its_rounds() {
+        let path = std::env::temp_dir().join(format!("append_{}.pgn", uuid::Uuid::new_v4()));
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 1);
+        config.pgn_path = Some(path.to_string_lossy().to_string());
+        let header = PgnHeader::from_config(&config);
+        let earlier: String = (1..=5)
+            .map(|round| format_pgn(&empty_game("1/2-1/2", round), &header))
+            .collect();
+        std::fs::write(&path, earlier).unwrap();
+        let (channels, _) = ArbiterChannels::new(100);
+        let arbiter = Arbiter::new(config, channels).await.unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(arbiter.config.round_offset, Some(5));
+        assert_eq!(pgn_round(&arbiter.config, 1), 6);
+    }
+
+    #[test]
+    fn round_offset_shifts_the_round_tag() {
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 4);
+        config.round_offset = Some(10);
+        let pgn = format_pgn(&empty_game("*", pgn_round(&config, 3)), &PgnHeader::from_config(&config));
+        assert!(pgn.contains("[Round \"13\"]"), "{}", pgn);
+    }
+
+    #[test]
+    fn pgn_header_uses_configured_site_and_extra_tags() {
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 1);
+        config.site_name = Some("Oslo".to_string());
+        config.country = Some("NOR".to_string());
+        config.extra_pgn_tags = Some(vec![("Annotator".to_string(), "Arbiter".to_string()), ("TimeControl".to_string(), "60+1".to_string())]);
+        validate_pgn_tags(&config).unwrap();
+        let pgn = format_pgn(&empty_game("*", 1), &PgnHeader::from_config(&config));
+        let tags: Vec<&str> = pgn.lines().take(9).collect();
+        assert_eq!(tags[1], "[Site \"Oslo NOR\"]");
+        assert_eq!(tags[6], "[Result \"*\"]");
+        assert_eq!(&tags[7..], ["[Annotator \"Arbiter\"]", "[TimeControl \"60+1\"]"]);
+
+        config.extra_pgn_tags = Some(vec![("Bad Tag".to_string(), "x".to_string())]);
+        assert!(validate_pgn_tags(&config).is_err());
+        for standard in ["White", "Result", "round"] {
+            config.extra_pgn_tags = Some(vec![(standard.to_string(), "x".to_string())]);
+            assert!(validate_pgn_tags(&config).is_err_and(|err| err.to_string().contains("written by the GUI")), "{}", standard);
+        }
+    }
+
+    #[test]
+    fn searchmoves_keep_only_legal_moves() {
+        let pos = Board::Standard(Chess::default());
+        let moves = ["e2e4", "e2e5", "g1f3", "nonsense"].map(String::from);
+        assert_eq!(legal_searchmoves(&pos, &moves, 1), vec!["e2e4", "g1f3"]);
+    }
+
+    #[test]
+    fn match_mode_names_the_engines_it_leaves_out() {
+        let mut config = test_config(TournamentMode::Match, &["A", "B", "C", "D"], 2);
+        assert_eq!(match_mode_benched(&config), vec!["C", "D"]);
+        config.mode = TournamentMode::RoundRobin;
+        assert!(match_mode_benched(&config).is_empty());
+    }
+
+    #[test]
+    fn go_style_picks_the_go_command_per_engine() {
+        let clock = || "go wtime 1000 btime 1000 winc 0 binc 0".to_string();
+        assert_eq!(go_command(GoStyle::Clock, None, 50, clock), "go wtime 1000 btime 1000 winc 0 binc 0");
+        assert_eq!(go_command(GoStyle::Movetime, None, 50, clock), "go movetime 50");
+        assert_eq!(go_command(GoStyle::InfiniteStop, None, 50, clock), "go infinite");
+        assert_eq!(go_command(GoStyle::Clock, Some(300), 300, clock), "go movetime 300");
+
+        let config = |style: &str| {
+            let mut config = test_config(TournamentMode::Match, &["infinite_stop", style], 1);
+            for engine in &mut config.engines {
+                engine.go_style = Some(engine.name.clone());
+            }
+            config
+        };
+        assert!(validate_go_styles(&config("movetime")).is_ok());
+        assert!(validate_go_styles(&config("ponder")).is_err());
+    }
+
+    #[test]
+    fn fischer_increment_is_added_after_every_move() {
+        assert_eq!(charge_clock(10_000, 300, 1_000, TimeControlType::Fischer), 10_700);
+        assert_eq!(charge_clock(10_000, 3_000, 1_000, TimeControlType::Fischer), 8_000);
+        assert_eq!(go_clock(10_000, 1_000, TimeControlType::Fischer), (10_000, 1_000));
+    }
+
+    #[test]
+    fn bronstein_delay_gives_back_only_the_time_used() {
+        assert_eq!(charge_clock(10_000, 300, 1_000, TimeControlType::Bronstein), 10_000);
+        assert_eq!(charge_clock(10_000, 3_000, 1_000, TimeControlType::Bronstein), 8_000);
+        assert_eq!(free_time(1_000, TimeControlType::Bronstein), 0);
+    }
+
+    #[test]
+    fn simple_delay_starts_the_clock_late() {
+        assert_eq!(charge_clock(10_000, 300, 1_000, TimeControlType::SimpleDelay), 10_000);
+        assert_eq!(charge_clock(10_000, 3_000, 1_000, TimeControlType::SimpleDelay), 8_000);
+        assert_eq!(charge_clock(500, 3_000, 1_000, TimeControlType::SimpleDelay), 0);
+        // UCI has no delay, so engines see it as extra clock time and no increment
+        assert_eq!(go_clock(10_000, 1_000, TimeControlType::SimpleDelay), (11_000, 0));
+    }
+
+    #[test]
+    fn habitual_overshoots_are_noted_in_the_search_summary() {
+        assert_eq!(soft_move_budget(60_000, 1_000, None), 4_000);
+        assert_eq!(soft_move_budget(60_000, 1_000, Some(500)), 500);
+
+        let mut search = EngineSearchStats::default();
+        for _ in 0..6 {
+            search.record_move(20, 1_000, 1_000);
+        }
+        search.overshot_moves = 2;
+        assert!(!format_search_summary(&search).contains("overshot"));
+        search.overshot_moves = 3;
+        assert!(format_search_summary(&search).ends_with(", overshot its time budget on 3 of 6 moves"));
+    }
+
+    #[test]
+    fn limit_strength_is_sent_before_the_elo() {
+        let engine: crate::types::EngineConfig = serde_json::from_value(serde_json::json!({
+            "name": "Weak", "path": "/bin/true",
+            "options": [["Hash", "64"], ["UCI_Elo", "1500"], ["UCI_LimitStrength", "true"]],
+        })).unwrap();
+        assert_eq!(setoption_commands(&engine, "standard", None), vec![
+            "setoption name UCI_LimitStrength value true",
+            "setoption name Hash value 64",
+            "setoption name UCI_Elo value 1500",
+        ]);
+
+        // limit_elo replaces any strength options given by hand
+        let engine = crate::types::EngineConfig { limit_elo: Some(1800), ..engine };
+        assert_eq!(setoption_commands(&engine, "standard", None), vec![
+            "setoption name UCI_LimitStrength value true",
+            "setoption name Hash value 64",
+            "setoption name UCI_Elo value 1800",
+        ]);
+    }
+
+    #[test]
+    fn uci_opponent_names_a_computer_without_title_or_rating() {
+        assert_eq!(uci_opponent_command("Stockfish 17"), "setoption name UCI_Opponent value none none computer Stockfish 17");
+    }
+
+    #[test]
+    fn null_bestmoves_are_told_apart_from_illegal_ones() {
+        assert!(is_null_bestmove(""));
+        assert!(is_null_bestmove("0000"));
+        assert!(!is_null_bestmove("e2e5"));
+    }
+
+    #[test]
+    fn game_progress_grows_without_reaching_one() {
+        assert_eq!(estimated_game_progress(0), 0.0);
+        assert_eq!(estimated_game_progress(80), 0.5);
+        let estimates: Vec<f64> = [10, 100, 1_000, 100_000].into_iter().map(estimated_game_progress).collect();
+        assert!(estimates.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(estimates[3] < 1.0);
+    }
+
+    #[test]
+    fn resign_agreement_needs_both_engines_past_the_threshold() {
+        assert_eq!(resign_agreement(Some(1200), Some(1050), 1000), Some(Color::White));
+        assert_eq!(resign_agreement(Some(-1000), Some(-1400), 1000), Some(Color::Black));
+        // One engine still sees a game, or they disagree on the winner
+        assert_eq!(resign_agreement(Some(1200), Some(300), 1000), None);
+        assert_eq!(resign_agreement(Some(1200), Some(-1200), 1000), None);
+        assert_eq!(resign_agreement(Some(1200), None, 1000), None);
+    }
+
+    #[test]
+    fn winning_streak_starts_over_when_the_winner_changes() {
+        let mut streak = WinningStreak::default();
+        for view in [Some(3), Some(5), Some(2)] {
+            streak.record(view);
+        }
+        assert_eq!((streak.moves, streak.winner()), (3, Color::White));
+        // Contradicting claims never build up a streak
+        for view in [Some(-4), Some(4), Some(-4)] {
+            streak.record(view);
+        }
+        assert_eq!((streak.moves, streak.winner()), (1, Color::Black));
+        streak.record(None);
+        assert_eq!(streak.moves, 0);
+    }
+
+    #[test]
+    fn resign_streak_restarts_on_an_eval_swing() {
+        // White-relative evals as the game loop records them: past the threshold or nothing
+        let past_threshold = |eval: i32| Some(eval).filter(|eval| eval.abs() >= 1000);
+        let mut streak = WinningStreak::default();
+        for eval in [1200, -1200, -1100] {
+            streak.record(past_threshold(eval));
+        }
+        assert_eq!((streak.moves, streak.winner()), (2, Color::Black));
+        streak.record(past_threshold(900));
+        assert_eq!(streak.moves, 0);
+    }
+
+    #[test]
+    fn white_relative_flips_black_scores() {
+        assert_eq!(white_relative(150, Color::Black), -150);
+        assert_eq!(white_relative(150, Color::White), 150);
+        assert_eq!(white_relative(-3, Color::Black), 3);
+    }
+
+    #[test]
+    fn movetime_must_be_positive_and_times_out_with_a_grace() {
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 2);
+        config.time_control.movetime_ms = Some(0);
+        assert!(validate_time_control(&config).is_err());
+        config.time_control.movetime_ms = Some(10);
+        assert!(validate_time_control(&config).is_ok());
+        assert_eq!(movetime_timeout_ms(10), 5050);
+    }
+
+    #[test]
+    fn chess960_positions_are_drawn_per_game_unless_scoped() {
+        let mut config = TournamentConfig { variant: "chess960".to_string(), ..test_config(TournamentMode::Match, &["A", "B"], 2) };
+        config.opening.seed = Some(7);
+        let games = [
+            Arbiter::make_schedule_item(&config, &[false, false], 0, 1, 0, 0, 1),
+            Arbiter::make_schedule_item(&config, &[false, false], 0, 1, 0, 1, 2),
+        ];
+        assert_eq!(chess960_sp(&config, &games[0]), None);
+
+        config.opening.chess960_scope = Some("pairing".to_string());
+        let sp = chess960_sp(&config, &games[0]);
+        assert!(sp.is_some());
+        assert_eq!(chess960_sp(&config, &games[1]), sp);
+
+        config.opening.chess960_sp = Some(518);
+        assert_eq!(chess960_sp(&config, &games[1]), Some(518));
+    }
+
+    #[test]
+    fn chess960_sp_518_is_the_standard_setup() {
+        assert_eq!(chess960_fen(518), STANDARD_START_FEN);
+        assert_eq!(chess960_fen(0), "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1");
+        assert_eq!(chess960_fen(959), "rkrnnqbb/pppppppp/8/8/8/8/PPPPPPPP/RKRNNQBB w KQkq - 0 1");
+    }
+
+    #[test]
+    fn preview_schedule_follows_pairing_order_and_colors() {
+        let mut config = test_config(TournamentMode::RoundRobin, &["A", "B", "C"], 2);
+        config.opening.fen = Some("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1".to_string());
+
+        let schedule = Arbiter::preview_schedule(&config).unwrap();
+        let games: Vec<(usize, &str, &str)> = schedule.iter()
+            .map(|game| (game.id, game.white_name.as_str(), game.black_name.as_str()))
+            .collect();
+        assert_eq!(games, vec![
+            (1, "A", "B"), (2, "B", "A"),
+            (3, "A", "C"), (4, "C", "A"),
+            (5, "B", "C"), (6, "C", "B"),
+        ]);
+        assert!(schedule.iter().all(|game| game.state == "Pending"
+            && game.start_fen.as_deref() == config.opening.fen.as_deref()));
+    }
+
+    #[test]
+    fn games_count_zero_needs_sprt_and_odd_counts_warn() {
+        let config = |games_count: u32, swap_sides: bool, sprt_enabled: bool| {
+            let mut config = test_config(TournamentMode::Match, &["A", "B"], games_count);
+            config.swap_sides = swap_sides;
+            config.sprt_enabled = sprt_enabled;
+            config
+        };
+
+        let err = Arbiter::preview_schedule(&config(0, true, false)).unwrap_err();
+        assert!(err.to_string().contains("requires SPRT"), "{}", err);
+        assert_eq!(Arbiter::preview_schedule(&config(0, true, true)).unwrap().len(), until_sprt_batch(&config(0, true, true)) as usize);
+
+        // Odd counts still play every game, alternating colors as usual
+        let schedule = Arbiter::preview_schedule(&config(3, true, false)).unwrap();
+        let whites: Vec<&str> = schedule.iter().map(|game| game.white_name.as_str()).collect();
+        assert_eq!(whites, vec!["A", "B", "A"]);
+        assert!(unbalanced_colors_warning(&config(3, true, false)).is_some_and(|message| message.contains("games_count 3 is odd")));
+        assert!(unbalanced_colors_warning(&config(4, true, false)).is_none());
+        assert!(unbalanced_colors_warning(&config(3, false, false)).is_none());
+        assert!(unbalanced_colors_warning(&config(1, false, false)).is_none());
+    }
+
+    #[tokio::test]
+    async fn setup_advisories_are_warnings_not_engine_errors() {
+        let path = std::env::temp_dir().join(format!("warnings_{}.pgn", uuid::Uuid::new_v4()));
+        let book = std::env::temp_dir().join(format!("openings_{}.epd", uuid::Uuid::new_v4()));
+        std::fs::write(&book, format!("{}\nnot a fen at all\n", STANDARD_START_FEN)).unwrap();
+        let mut config = test_config(TournamentMode::Match, &["A", "B", "C"], 3);
+        config.pgn_path = Some(path.to_string_lossy().to_string());
+        config.opening.file = Some(book.to_string_lossy().to_string());
+        let (channels, ArbiterReceivers { mut error_rx, mut warning_rx, .. }) = ArbiterChannels::new(100);
+        let _arbiter = Arbiter::new(config, channels).await.unwrap();
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&book);
+
+        let warnings: Vec<String> = std::iter::from_fn(|| warning_rx.try_recv().ok()).map(|warning| warning.message).collect();
+        assert!(warnings.iter().any(|message| message.contains("C will not play")), "{:?}", warnings);
+        assert!(warnings.iter().any(|message| message.contains("games_count 3 is odd")), "{:?}", warnings);
+        assert!(warnings.iter().any(|message| message.contains("Skipped 1 invalid opening(s)")), "{:?}", warnings);
+        assert!(error_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn gauntlet_seed_plays_as_many_whites_as_blacks() {
+        let config = test_config(TournamentMode::Gauntlet, &["Seed", "B", "C", "D", "E"], 3);
+
+        let schedule = Arbiter::preview_schedule(&config).unwrap();
+        let seed_whites = schedule.iter().filter(|game| game.white_name == "Seed").count();
+        assert_eq!(schedule.len(), 12);
+        assert_eq!(seed_whites, 6);
+        // Each opponent still gets both colors, starting alternately
+        let first_whites: Vec<&str> = schedule.iter().step_by(3).map(|game| game.white_name.as_str()).collect();
+        assert_eq!(first_whites, vec!["Seed", "C", "Seed", "E"]);
+    }
+
+    #[test]
+    fn out_of_range_gauntlet_seeds_are_rejected() {
+        let mut config = test_config(TournamentMode::Gauntlet, &["Seed", "B", "C"], 2);
+        config.gauntlet_seeds = Some(vec![0, 3]);
+        let err = Arbiter::preview_schedule(&config).unwrap_err();
+        assert!(err.to_string().contains("Gauntlet seed 3 is out of range"), "{}", err);
+
+        // Seeds only matter to gauntlets
+        config.mode = TournamentMode::RoundRobin;
+        assert!(Arbiter::preview_schedule(&config).is_ok());
+    }
+
+    #[test]
+    fn double_round_robin_alternates_colors_per_cycle() {
+        let mut config = test_config(TournamentMode::RoundRobin, &["A", "B", "C"], 1);
+        config.cycles = Some(2);
+
+        let schedule = Arbiter::preview_schedule(&config).unwrap();
+        let games: Vec<(&str, &str)> = schedule.iter()
+            .map(|game| (game.white_name.as_str(), game.black_name.as_str()))
+            .collect();
+        assert_eq!(games, vec![("A", "B"), ("A", "C"), ("B", "C"), ("B", "A"), ("C", "A"), ("C", "B")]);
+
+        let pairings = Arbiter::generate_pairings(&config);
+        assert_eq!(compute_game_mapping(&pairings, config.games_count, 5), Some((0, 2, 1, 0)));
+        // A second repetition starts over at the first pairing with the next game index
+        assert_eq!(compute_game_mapping(&pairings, config.games_count, 7), Some((0, 1, 0, 1)));
+        assert_eq!((game_repetition(&pairings, config.games_count, 6), game_repetition(&pairings, config.games_count, 7)), (1, 2));
+    }
+
+    #[test]
+    fn rule_endings_close_the_pgn_with_a_comment() {
+        assert_eq!(ending_comment(TerminationReason::Checkmate, "0-1").as_deref(), Some("Black mates"));
+        assert_eq!(ending_comment(TerminationReason::Stalemate, "1/2-1/2").as_deref(), Some("Draw by stalemate"));
+        assert_eq!(ending_comment(TerminationReason::ResignAdjudication, "1-0"), None);
+    }
+
+    #[test]
+    fn duplicate_engine_names_and_ids_are_rejected() {
+        let config = |engines: &[(&str, Option<&str>)]| {
+            let names: Vec<&str> = engines.iter().map(|(name, _)| *name).collect();
+            let mut config = test_config(TournamentMode::RoundRobin, &names, 1);
+            for (engine, (_, id)) in config.engines.iter_mut().zip(engines) {
+                engine.id = id.map(str::to_string);
+            }
+            config
+        };
+
+        assert!(Arbiter::validate_engine_identities(&config(&[("A", None), ("B", None), ("C", None)])).is_ok());
+        assert!(Arbiter::validate_engine_identities(&config(&[("A", None), ("A", None)])).is_err());
+        assert!(Arbiter::validate_engine_identities(&config(&[("A", Some("x")), ("B", Some("x"))])).is_err());
+    }
+
+    #[tokio::test]
+    async fn resumed_schedule_replays_finished_games_into_stats() {
+        let config = test_config(TournamentMode::Match, &["A", "B"], 4);
+        let (channels, ArbiterReceivers { mut tourney_stats_rx, .. }) = ArbiterChannels::new(100);
+        let arbiter = Arbiter::new(config.clone(), channels).await.unwrap();
+        let mut schedule = Arbiter::preview_schedule(&config).unwrap();
+        // A wins with White, wins with Black by forfeit, then draws; game 4 never finished
+        for (game, (state, result)) in schedule.iter_mut().zip([("Finished", "1-0"), ("Skipped", "0-1 (forfeit)"), ("Finished", "1/2-1/2")]) {
+            game.state = state.to_string();
+            game.result = Some(result.to_string());
+        }
+        schedule[3].state = "Active".to_string();
+
+        arbiter.load_schedule_state(schedule).await;
+
+        let stats = tourney_stats_rx.try_recv().unwrap();
+        assert_eq!((stats.wins, stats.draws, stats.losses, stats.total_games), (2, 1, 0, 3));
+        let a = stats.standings.entries.iter().find(|entry| entry.engine_name == "A").unwrap();
+        assert_eq!(a.points, 2.5);
+    }
+
+    #[tokio::test]
+    async fn time_limit_removes_resumed_pending_games() {
+        let path = std::env::temp_dir().join(format!("resume_{}.json", uuid::Uuid::new_v4()));
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 2);
+        config.resume_state_path = Some(path.to_string_lossy().to_string());
+        config.resume_from_state = true;
+        config.max_duration_secs = Some(0);
+        let (channels, _) = ArbiterChannels::new(100);
+        let arbiter = Arbiter::new(config.clone(), channels).await.unwrap();
+        let mut schedule = Arbiter::preview_schedule(&config).unwrap();
+        schedule[0].state = "Finished".to_string();
+        schedule[0].result = Some("1-0".to_string());
+        arbiter.load_schedule_state(schedule).await;
+
+        let complete = arbiter.run_tournament().await.unwrap();
+        let exists = path.exists();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(complete.reason, CompletionReason::TimeLimitReached);
+        let states: Vec<String> = arbiter.schedule_state.lock().await.iter().map(|game| game.state.clone()).collect();
+        assert_eq!(states, vec!["Finished", "Removed"]);
+        assert!(!exists, "nothing is left to resume");
+    }
+
+    #[tokio::test]
+    async fn pausing_after_the_run_saves_nothing() {
+        let path = std::env::temp_dir().join(format!("resume_{}.json", uuid::Uuid::new_v4()));
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 2);
+        config.resume_state_path = Some(path.to_string_lossy().to_string());
+        config.max_duration_secs = Some(0);
+        let (channels, _) = ArbiterChannels::new(100);
+        let arbiter = Arbiter::new(config, channels).await.unwrap();
+        arbiter.run_tournament().await.unwrap();
+        assert!(!path.exists());
+
+        arbiter.set_paused(true).await;
+        let exists = path.exists();
+        let _ = std::fs::remove_file(&path);
+        assert!(!exists, "a finished run must not leave a resume file behind");
+    }
+
+    #[tokio::test]
+    async fn shutdown_persists_active_games_as_pending() {
+        let path = std::env::temp_dir().join(format!("resume_{}.json", uuid::Uuid::new_v4()));
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 2);
+        config.resume_state_path = Some(path.to_string_lossy().to_string());
+        let (channels, _) = ArbiterChannels::new(100);
+        let arbiter = Arbiter::new(config.clone(), channels).await.unwrap();
+        let mut schedule = Arbiter::preview_schedule(&config).unwrap();
+        schedule[0].state = "Finished".to_string();
+        schedule[0].result = Some("1-0".to_string());
+        schedule[1].state = "Active".to_string();
+        arbiter.load_schedule_state(schedule).await;
+
+        arbiter.shutdown().await.unwrap();
+
+        let saved: TournamentResumeState = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let states: Vec<&str> = saved.schedule.iter().map(|game| game.state.as_str()).collect();
+        assert_eq!(states, vec!["Finished", "Pending"]);
+        assert!(*arbiter.should_stop.lock().await);
+    }
+
+    #[tokio::test]
+    async fn paused_flag_round_trips_through_the_resume_state() {
+        let path = std::env::temp_dir().join(format!("resume_{}.json", uuid::Uuid::new_v4()));
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 2);
+        config.resume_state_path = Some(path.to_string_lossy().to_string());
+        let new_arbiter = || async {
+            let (channels, _) = ArbiterChannels::new(100);
+            Arbiter::new(config.clone(), channels).await.unwrap()
+        };
+        let load = || -> TournamentResumeState { serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap() };
+
+        let arbiter = new_arbiter().await;
+        let mut schedule = Arbiter::preview_schedule(&config).unwrap();
+        schedule[0].state = "Finished".to_string();
+        schedule[0].result = Some("1-0".to_string());
+        arbiter.load_schedule_state(schedule).await;
+        arbiter.set_paused(true).await;
+        arbiter.shutdown().await.unwrap();
+        let saved = load();
+        assert!(saved.paused);
+
+        // What `resume_match` does with the saved state
+        let resumed = new_arbiter().await;
+        resumed.load_schedule_state(saved.schedule).await;
+        resumed.set_paused(saved.paused).await;
+        assert!(*resumed.is_paused.lock().await);
+        assert_eq!(resumed.schedule_state.lock().await[0].result.as_deref(), Some("1-0"));
+
+        resumed.set_paused(false).await;
+        assert!(!load().paused);
+        let _ = std::fs::remove_file(&path);
+
+        // Files saved before the flag existed resume unpaused
+        let mut legacy = serde_json::to_value(TournamentResumeState { config: config.clone(), schedule: Vec::new(), paused: true }).unwrap();
+        legacy.as_object_mut().unwrap().remove("paused");
+        assert!(!serde_json::from_value::<TournamentResumeState>(legacy).unwrap().paused);
+    }
+
+    #[tokio::test]
+    async fn prioritized_pairing_jumps_the_queue() {
+        let config = test_config(TournamentMode::RoundRobin, &["A", "B", "C"], 2);
+        let (channels, _) = ArbiterChannels::new(100);
+        let arbiter = Arbiter::new(config, channels).await.unwrap();
+        arbiter.update_remaining_rounds(2).await.unwrap();
+
+        assert_eq!(arbiter.prioritize_pairing(2, 1).await.unwrap(), 2);
+        assert!(arbiter.prioritize_pairing(1, 1).await.is_err());
+
+        let queue = &arbiter.schedule.lock().await.queue;
+        let games: Vec<(&str, &str)> = queue.iter()
+            .map(|game| (game.white_name.as_str(), game.black_name.as_str()))
+            .collect();
+        assert_eq!(games, vec![("B", "C"), ("C", "B"), ("A", "B"), ("B", "A"), ("A", "C"), ("C", "A")]);
+    }
+
+    #[tokio::test]
+    async fn cancelled_pairing_is_not_scheduled_again() {
+        let config = test_config(TournamentMode::RoundRobin, &["A", "B", "C"], 2);
+        let (channels, ArbiterReceivers { mut schedule_update_rx, .. }) = ArbiterChannels::new(100);
+        let arbiter = Arbiter::new(config, channels).await.unwrap();
+        arbiter.update_remaining_rounds(2).await.unwrap();
+        while schedule_update_rx.try_recv().is_ok() {}
+
+        assert_eq!(arbiter.cancel_pairing(2, 0).await.unwrap(), 2);
+        assert!(arbiter.cancel_pairing(0, 3).await.is_err());
+        let removed: Vec<(usize, String)> = std::iter::from_fn(|| schedule_update_rx.try_recv().ok())
+            .map(|game| (game.id, game.state))
+            .collect();
+        assert_eq!(removed, vec![(3, "Removed".to_string()), (4, "Removed".to_string())]);
+
+        // Topping up leaves the cancelled pairing out
+        arbiter.update_remaining_rounds(3).await.unwrap();
+        let queue = &arbiter.schedule.lock().await.queue;
+        assert_eq!(queue.len(), 6);
+        assert!(queue.iter().all(|game| (game.idx_a, game.idx_b) != (0, 2)));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn schedule_stays_consistent_under_concurrent_updates() {
+        let config = test_config(TournamentMode::RoundRobin, &["A", "B", "C", "D"], 2);
+        let (channels, _) = ArbiterChannels::new(100);
+        let arbiter = Arc::new(Arbiter::new(config, channels).await.unwrap());
+        arbiter.update_remaining_rounds(2).await.unwrap();
+
+        // The scheduling loop's side: take games off the front as slots free up
+        let scheduler = {
+            let arbiter = arbiter.clone();
+            tokio::spawn(async move {
+                let mut started = Vec::new();
+                for _ in 0..2000 {
+                    if let Some(game) = { arbiter.schedule.lock().await.queue.pop_front() } {
+                        started.push(game);
+                    }
+                    tokio::task::yield_now().await;
+                }
+                started
+            })
+        };
+        let updaters: Vec<_> = (0..4usize).map(|worker| {
+            let arbiter = arbiter.clone();
+            tokio::spawn(async move {
+                for round in 0..250u32 {
+                    arbiter.update_remaining_rounds((round + worker as u32) % 5).await.unwrap();
+                    arbiter.prioritize_pairing(worker % 4, (worker + 1) % 4).await.unwrap();
+                }
+            })
+        }).collect();
+
+        let started = tokio::time::timeout(std::time::Duration::from_secs(30), async {
+            for updater in updaters { updater.await.unwrap(); }
+            scheduler.await.unwrap()
+        }).await.expect("schedule updates deadlocked");
+
+        let schedule = arbiter.schedule.lock().await;
+        let mut ids = HashSet::new();
+        let mut slots = HashSet::new();
+        for game in started.iter().chain(schedule.queue.iter()) {
+            assert!(ids.insert(game.id), "game {} handed out twice", game.id);
+            assert!(game.id <= schedule.next_game_id);
+            // Removals only rewind over games nobody took, so no slot is reused
+            assert!(slots.insert((game.idx_a, game.idx_b, game.cycle, game.game_idx)), "slot of game {} reused", game.id);
+        }
+        for state in &schedule.pairing_states {
+            let taken = started.iter().chain(schedule.queue.iter())
+                .filter(|game| (game.idx_a, game.idx_b, game.cycle) == (state.idx_a, state.idx_b, state.cycle))
+                .count();
+            assert!(taken <= state.next_game_idx as usize);
+        }
+    }
+
+    #[tokio::test]
+    async fn reenabled_engine_starts_over_on_failures() {
+        let mut config = test_config(TournamentMode::Match, &["A", "B"], 2);
+        for engine in &mut config.engines {
+            engine.id = Some(engine.name.clone());
+        }
+        config.spawn_failure_limit = Some(2);
+        let (channels, _) = ArbiterChannels::new(100);
+        let arbiter = Arbiter::new(config.clone(), channels).await.unwrap();
+        let (report_tx, mut report_rx) = mpsc::channel(10);
+        let context = FailureContext {
+            engine_spawn_failures: &arbiter.engine_spawn_failures,
+            disabled_engine_ids: &arbiter.disabled_engine_ids,
+            error_tx: &report_tx,
+            tourney_stats: &arbiter.tourney_stats,
+            stop_on_disable: None,
+            failure_limit: spawn_failure_limit(&config),
+        };
+        let fail = || report_engine_failure(&context, &config.engines[1], 1, "spawn failed".to_string());
+
+        fail().await;
+        assert!(!report_rx.try_recv().unwrap().disabled);
+        fail().await;
+        assert!(report_rx.try_recv().unwrap().disabled);
+        assert!(arbiter.disabled_engine_ids.lock().await.contains("B"));
+
+        // Taken out of the set by the UI: one more failure doesn't disable it again
+        arbiter.set_disabled_engine_ids(Vec::new()).await;
+        fail().await;
+        let report = report_rx.try_recv().unwrap();
+        assert_eq!((report.failure_count, report.disabled), (1, false));
+
+        fail().await;
+        assert!(report_rx.try_recv().unwrap().disabled);
+        arbiter.reenable_engine("B").await;
+        assert!(arbiter.disabled_engine_ids.lock().await.is_empty());
+        assert!(arbiter.engine_spawn_failures.lock().await.is_empty());
+
+        let zero_tolerance = TournamentConfig { spawn_failure_limit: Some(0), ..config.clone() };
+        assert_eq!(spawn_failure_limit(&zero_tolerance), 1);
+        assert_eq!(spawn_failure_limit(&TournamentConfig { spawn_failure_limit: None, ..config }), 3);
+    }
+
+    #[tokio::test]
+    async fn extended_rounds_continue_the_book_after_removals() {
+        let config = test_config(TournamentMode::Match, &["A", "B"], 4);
+        let (channels, ArbiterReceivers { mut schedule_update_rx, .. }) = ArbiterChannels::new(100);
+        let arbiter = Arbiter::new(config, channels).await.unwrap();
+
+        arbiter.update_remaining_rounds(4).await.unwrap();
+        arbiter.update_remaining_rounds(1).await.unwrap();
+        arbiter.update_remaining_rounds(4).await.unwrap();
+        while schedule_update_rx.try_recv().is_ok() {}
+
+        let queue = &arbiter.schedule.lock().await.queue;
+        let lines: Vec<(usize, &str)> = queue.iter()
+            .map(|game| (game.opening_index, game.white_name.as_str()))
+            .collect();
+        assert_eq!(lines, vec![(0, "A"), (0, "B"), (1, "A"), (1, "B")]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn invalid_generated_start_is_rejected_for_a_new_draw() {
+        let config = TournamentConfig { variant: "chess960".to_string(), ..test_config(TournamentMode::Match, &["A", "B"], 2) };
+        let engine = AsyncEngine::spawn("/bin/cat").await.unwrap();
+        let (game_update_tx, _game_update_rx) = mpsc::channel(100);
+        let (time_update_tx, _time_update_rx) = mpsc::channel(100);
+        let flag = Arc::new(Mutex::new(false));
+        let active_engines = Arc::new(Mutex::new(Vec::new()));
+        let active_games = Arc::new(Mutex::new(HashMap::new()));
+        let play = |regenerable_start| play_game_static(
+            &engine, &engine, 0, 1, "not a fen", &config, &game_update_tx, &time_update_tx, None,
+            &flag, &flag, &flag, &active_engines, &active_games, 0, regenerable_start,
+        );
+
+        let generated = play(true).await.err().unwrap();
+        assert!(generated.downcast_ref::<StartPositionRejected>().is_some());
+        // A start the user picked is an error to report, not one to redraw
+        let assigned = play(false).await.err().unwrap();
+        assert!(assigned.downcast_ref::<StartPositionRejected>().is_none());
+        let _ = engine.kill().await;
+    }
+}
@@ -0,0 +1,77 @@
+use crate::arbiter::initialize_engine;
+use crate::epd::search;
+use crate::uci::AsyncEngine;
+use crate::verify::engine_at;
+use serde::Serialize;
+use shakmaty::fen::Fen;
+use shakmaty::{CastlingMode, Chess};
+
+/// Used when the caller doesn't pass its own positions: the opening, a few
+/// middlegames and an endgame, so one slow phase can't hide behind the others.
+pub const BENCH_POSITIONS: &[&str] = &[
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 10",
+    "r1bq1rk1/pp2bppp/2n1pn2/2pp4/3P4/2PBPN2/PP1N1PPP/R2QK2R w KQ - 0 8",
+    "4rrk1/pp1n3p/3q2pQ/2p1pb2/2PP4/2P3N1/P2B2PP/4RRK1 b - - 7 19",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+];
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BenchPosition {
+    pub fen: String,
+    pub depth: u32,
+    pub nodes: u64,
+    pub nps: u64, // 0 when the engine reported none
+    pub bestmove: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BenchResult {
+    pub positions: Vec<BenchPosition>,
+    pub total_nodes: u64,
+    pub average_nps: Option<u64>, // over the positions that reported nps
+}
+
+/// Searches each of `positions` (or `BENCH_POSITIONS`) for `movetime_ms` with
+/// the engine at `path` and collects the nodes, nps and depth it reports.
+pub async fn benchmark_engine(path: &str, positions: Option<Vec<String>>, movetime_ms: u64) -> anyhow::Result<BenchResult> {
+    if movetime_ms == 0 {
+        return Err(anyhow::anyhow!("movetime_ms must be at least 1 ms"));
+    }
+    let fens = positions.unwrap_or_else(|| BENCH_POSITIONS.iter().map(|fen| fen.to_string()).collect());
+    if fens.is_empty() {
+        return Err(anyhow::anyhow!("No benchmark positions given"));
+    }
+    for fen in &fens {
+        Fen::from_ascii(fen.as_bytes()).ok()
+            .and_then(|setup| setup.into_position::<Chess>(CastlingMode::Standard).ok())
+            .ok_or_else(|| anyhow::anyhow!("Invalid benchmark FEN: {}", fen))?;
+    }
+
+    let config = engine_at(path);
+    let process = AsyncEngine::spawn(path).await?;
+    if let Err(err) = initialize_engine(&process, &config, 0, "standard", None, None).await {
+        let _ = process.kill().await;
+        return Err(anyhow::anyhow!("Handshake failed: {}", err));
+    }
+
+    let mut result = BenchResult::default();
+    for fen in fens {
+        let _ = process.send("ucinewgame".to_string()).await;
+        let outcome = match search(&process, &fen, movetime_ms).await {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                let _ = process.kill().await;
+                return Err(anyhow::anyhow!("Search failed on {}: {}", fen, err));
+            }
+        };
+        result.total_nodes += outcome.nodes;
+        result.positions.push(BenchPosition { fen, depth: outcome.depth, nodes: outcome.nodes, nps: outcome.nps, bestmove: outcome.bestmove });
+    }
+    let nps_samples: Vec<u64> = result.positions.iter().map(|position| position.nps).filter(|nps| *nps > 0).collect();
+    if !nps_samples.is_empty() {
+        result.average_nps = Some(nps_samples.iter().sum::<u64>() / nps_samples.len() as u64);
+    }
+    let _ = process.quit().await;
+    Ok(result)
+}
@@ -0,0 +1,133 @@
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Arbiter decisions for one game, written as `game_<id>.diag.json` when
+/// `diagnostics` is enabled. Meant to be attached to bug reports, so it records
+/// what the arbiter saw and decided rather than raw UCI traffic.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct GameDiagnostics {
+    pub game_id: usize,
+    pub white: String,
+    pub black: String,
+    pub start_fen: String,
+    pub opening_source: String,
+    pub white_options: Vec<String>,
+    pub black_options: Vec<String>,
+    pub moves: Vec<MoveDiagnostics>,
+    pub engine_restarts: Vec<String>,
+    pub white_lagged_lines: u64,
+    pub black_lagged_lines: u64,
+    pub termination: Option<Termination>,
+    pub result: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct MoveDiagnostics {
+    pub ply: usize,
+    pub side: String,
+    pub bestmove: String,
+    pub score_cp: Option<i32>,
+    pub score_mate: Option<i32>,
+    pub depth: u32,
+    pub nodes: u64,
+    pub time_ms: i64,
+    pub budget_ms: i64,
+    pub overshoot_ms: i64, // time_ms - budget_ms; negative when inside the budget
+    pub white_clock_ms: i64,
+    pub black_clock_ms: i64,
+    pub halfmove_clock: u32,
+    pub resign_count: u32,
+    pub draw_count: u32,
+    pub mate_count: u32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Termination {
+    pub reason: TerminationReason,
+    pub detail: String,
+}
+
+/// Why the arbiter ended a game. Serialized, logged and sent to the UI as the
+/// lowercase phrase from `as_str`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerminationReason {
+    Checkmate,
+    Stalemate,
+    InsufficientMaterial,
+    ThreefoldRepetition,
+    FiftyMoveRule,
+    SeventyFiveMoveRule,
+    MateAdjudication,
+    ResignAdjudication,
+    DrawAdjudication,
+    TimeForfeit,
+    IllegalMove,
+    EngineDisconnected,
+    ProtocolViolation,
+}
+
+impl TerminationReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TerminationReason::Checkmate => "checkmate",
+            TerminationReason::Stalemate => "stalemate",
+            TerminationReason::InsufficientMaterial => "insufficient material",
+            TerminationReason::ThreefoldRepetition => "threefold repetition",
+            TerminationReason::FiftyMoveRule => "50-move rule",
+            TerminationReason::SeventyFiveMoveRule => "75-move rule",
+            TerminationReason::MateAdjudication => "mate adjudication",
+            TerminationReason::ResignAdjudication => "resign adjudication",
+            TerminationReason::DrawAdjudication => "draw adjudication",
+            TerminationReason::TimeForfeit => "time forfeit",
+            TerminationReason::IllegalMove => "illegal move",
+            TerminationReason::EngineDisconnected => "engine disconnected",
+            TerminationReason::ProtocolViolation => "protocol violation",
+        }
+    }
+}
+
+impl fmt::Display for TerminationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for TerminationReason {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl GameDiagnostics {
+    pub fn record_move(&mut self, entry: MoveDiagnostics) {
+        self.moves.push(entry);
+    }
+
+    /// Adjudication counters after the latest move was scored.
+    pub fn set_counters(&mut self, resign_count: u32, draw_count: u32, mate_count: u32) {
+        if let Some(last) = self.moves.last_mut() {
+            last.resign_count = resign_count;
+            last.draw_count = draw_count;
+            last.mate_count = mate_count;
+        }
+    }
+
+    pub fn terminate(&mut self, reason: TerminationReason, detail: String) {
+        self.termination = Some(Termination { reason, detail });
+    }
+
+    /// `game_<id>.diag.json` next to the tournament PGN.
+    pub fn file_path(pgn_path: Option<&str>, game_id: usize) -> PathBuf {
+        let dir = pgn_path
+            .and_then(|path| Path::new(path).parent())
+            .unwrap_or_else(|| Path::new(""));
+        dir.join(format!("game_{}.diag.json", game_id))
+    }
+
+    pub async fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        tokio::fs::write(path, json).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use shakmaty::fen::Fen;
+use shakmaty::uci::Uci;
+use shakmaty::{CastlingMode, Chess, EnPassantMode, Position};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const ECO_TABLE: &str = include_str!("eco.tsv");
+
+/// Table lines indexed by the position they reach, so games that start from a
+/// book FEN or transpose still get classified.
+fn eco_positions() -> &'static HashMap<String, (String, String)> {
+    static POSITIONS: OnceLock<HashMap<String, (String, String)>> = OnceLock::new();
+    POSITIONS.get_or_init(|| {
+        let mut positions = HashMap::new();
+        for line in ECO_TABLE.lines() {
+            if line.trim().is_empty() || line.starts_with('#') { continue; }
+            let mut fields = line.split('\t');
+            let (Some(eco), Some(name), Some(moves)) = (fields.next(), fields.next(), fields.next()) else { continue };
+            let mut pos = Chess::default();
+            let mut legal = true;
+            for mv in moves.split_whitespace() {
+                match mv.parse::<Uci>().ok().and_then(|uci| uci.to_move(&pos).ok()) {
+                    Some(m) => pos.play_unchecked(&m),
+                    None => { legal = false; break; }
+                }
+            }
+            if legal {
+                positions.insert(position_key(&pos), (eco.to_string(), name.to_string()));
+            }
+        }
+        positions
+    })
+}
+
+fn position_key(pos: &Chess) -> String {
+    let fen = Fen::from_position(pos.clone(), EnPassantMode::Legal).to_string();
+    fen.split_whitespace().take(4).collect::<Vec<_>>().join(" ")
+}
+
+/// Returns `(eco, opening name)` for the deepest table position reached by the
+/// game, or `None` if it never passes through a known position.
+pub fn classify(start_fen: &str, moves: &[String]) -> Option<(String, String)> {
+    let positions = eco_positions();
+    let setup = Fen::from_ascii(start_fen.as_bytes()).ok()?;
+    let mut pos: Chess = setup.into_position(CastlingMode::Standard).ok()?;
+    let mut found = positions.get(&position_key(&pos)).cloned();
+    for mv in moves {
+        let Some(m) = mv.parse::<Uci>().ok().and_then(|uci| uci.to_move(&pos).ok()) else { break };
+        pos.play_unchecked(&m);
+        if let Some(entry) = positions.get(&position_key(&pos)) {
+            found = Some(entry.clone());
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    fn moves(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn every_table_line_is_legal() {
+        let lines = ECO_TABLE.lines().filter(|l| !l.trim().is_empty() && !l.starts_with('#')).count();
+        assert_eq!(eco_positions().len(), lines);
+    }
+
+    #[test]
+    fn classifies_deepest_known_position() {
+        let game = moves("e2e4 e7e5 g1f3 g8f6 f3e5 d7d6");
+        assert_eq!(classify(START, &game), Some(("C42".to_string(), "Petroff Defence".to_string())));
+    }
+
+    #[test]
+    fn classifies_transpositions_and_book_fens() {
+        // 1.Nf3 d5 2.d4 reaches the Queen's Pawn Game by transposition
+        assert_eq!(classify(START, &moves("g1f3 d7d5 d2d4 g8f6 c1f4")).map(|(eco, _)| eco), Some("D02".to_string()));
+        let after_e4_c5 = "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2";
+        assert_eq!(classify(after_e4_c5, &moves("g1f3 d7d6")).map(|(eco, _)| eco), Some("B50".to_string()));
+    }
+
+    #[test]
+    fn unknown_openings_are_unclassified() {
+        assert_eq!(classify(START, &moves("h2h4 h7h5")), None);
+    }
+}
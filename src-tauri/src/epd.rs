@@ -0,0 +1,190 @@
+use crate::arbiter::{initialize_engine, movetime_timeout_ms, output_buffer_lines, parse_info};
+use crate::types::EngineConfig;
+use crate::uci::AsyncEngine;
+use serde::Serialize;
+use shakmaty::fen::Fen;
+use shakmaty::san::SanPlus;
+use shakmaty::{CastlingMode, Chess};
+use std::path::Path;
+use tokio::sync::broadcast;
+use tokio::time::{timeout, Duration};
+
+/// One test position: the setup plus its `bm`/`am` moves, already in UCI.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EpdPosition {
+    pub id: Option<String>,
+    pub fen: String,
+    pub best_moves: Vec<String>,
+    pub avoid_moves: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct EpdResult {
+    pub id: Option<String>,
+    pub fen: String,
+    pub bestmove: Option<String>,
+    pub solved: bool,
+}
+
+/// Score of one engine over a test suite.
+#[derive(Clone, Debug, Serialize)]
+pub struct EpdReport {
+    pub engine: String,
+    pub movetime_ms: u64,
+    pub solved: u32,
+    pub total: u32,
+    pub results: Vec<EpdResult>,
+}
+
+impl EpdPosition {
+    /// A move solves the position when it is one of the `bm` moves (if any are
+    /// given) and none of the `am` moves.
+    pub fn is_solved_by(&self, bestmove: &str) -> bool {
+        (self.best_moves.is_empty() || self.best_moves.iter().any(|mv| mv == bestmove))
+            && !self.avoid_moves.iter().any(|mv| mv == bestmove)
+    }
+}
+
+/// Parses `<board> <side> <castling> <ep> bm Nf3; am Qxb2; id "WAC.001";`. Lines
+/// without a legal setup or without any `bm`/`am` move are skipped.
+pub fn parse_epd_line(line: &str) -> Option<EpdPosition> {
+    let line = line.trim();
+    let mut fields = line.splitn(5, char::is_whitespace);
+    let setup: Vec<&str> = fields.by_ref().take(4).collect();
+    if setup.len() < 4 {
+        return None;
+    }
+    let fen = format!("{} 0 1", setup.join(" "));
+    let pos: Chess = Fen::from_ascii(fen.as_bytes()).ok()?.into_position(CastlingMode::Standard).ok()?;
+
+    let mut position = EpdPosition { id: None, fen, best_moves: Vec::new(), avoid_moves: Vec::new() };
+    for operation in fields.next().unwrap_or("").split(';') {
+        let operation = operation.trim();
+        let (opcode, operands) = operation.split_once(char::is_whitespace).unwrap_or((operation, ""));
+        let to_uci = |san: &str| {
+            let m = SanPlus::from_ascii(san.as_bytes()).ok()?.san.to_move(&pos).ok()?;
+            Some(m.to_uci(CastlingMode::Standard).to_string())
+        };
+        match opcode {
+            "bm" => position.best_moves.extend(operands.split_whitespace().filter_map(to_uci)),
+            "am" => position.avoid_moves.extend(operands.split_whitespace().filter_map(to_uci)),
+            "id" => position.id = Some(operands.trim().trim_matches('"').to_string()),
+            _ => {}
+        }
+    }
+    if position.best_moves.is_empty() && position.avoid_moves.is_empty() {
+        return None;
+    }
+    Some(position)
+}
+
+pub fn load_epd_suite(path: &str) -> anyhow::Result<Vec<EpdPosition>> {
+    let data = std::fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to open EPD suite {}: {}", path, e))?;
+    let positions: Vec<EpdPosition> = data.lines().filter_map(parse_epd_line).collect();
+    if positions.is_empty() {
+        return Err(anyhow::anyhow!("No positions with bm/am moves found in {}", path));
+    }
+    Ok(positions)
+}
+
+/// Runs `engine` on every position of the suite at `path` for `movetime_ms` each.
+/// An engine that crashes or overruns the move time fails that position and the
+/// rest of the suite continues with a fresh process.
+pub async fn run_epd_suite(engine: &EngineConfig, path: &str, movetime_ms: u64) -> anyhow::Result<EpdReport> {
+    let positions = load_epd_suite(path)?;
+    let movetime_ms = movetime_ms.max(1);
+    let mut process = start_engine(engine).await?;
+
+    let mut results = Vec::with_capacity(positions.len());
+    for position in &positions {
+        let bestmove = match search(&process, &position.fen, movetime_ms).await {
+            Ok(outcome) => outcome.bestmove,
+            Err(err) => {
+                tracing::warn!("EPD {}: {} failed: {}", position.id.as_deref().unwrap_or(&position.fen), engine.name, err);
+                let _ = process.kill().await;
+                process = start_engine(engine).await?;
+                None
+            }
+        };
+        let solved = bestmove.as_deref().is_some_and(|mv| position.is_solved_by(mv));
+        results.push(EpdResult { id: position.id.clone(), fen: position.fen.clone(), bestmove, solved });
+    }
+    let _ = process.quit().await;
+
+    Ok(EpdReport {
+        engine: engine.name.clone(),
+        movetime_ms,
+        solved: results.iter().filter(|result| result.solved).count() as u32,
+        total: results.len() as u32,
+        results,
+    })
+}
+
+async fn start_engine(engine: &EngineConfig) -> anyhow::Result<AsyncEngine> {
+    let process = AsyncEngine::spawn_with_buffer(&engine.path, engine.working_directory.as_deref().map(Path::new), output_buffer_lines(engine)).await?;
+    initialize_engine(&process, engine, 0, "standard", None, None).await?;
+    Ok(process)
+}
+
+/// One `go movetime` answer: the move, `None` for a null move, and the highest
+/// nps, nodes and depth the engine reported.
+pub(crate) struct SearchOutcome {
+    pub bestmove: Option<String>,
+    pub nps: u64,
+    pub nodes: u64,
+    pub depth: u32,
+}
+
+pub(crate) async fn search(engine: &AsyncEngine, fen: &str, movetime_ms: u64) -> anyhow::Result<SearchOutcome> {
+    let mut rx = engine.stdout_broadcast.subscribe();
+    engine.send(format!("position fen {}", fen)).await?;
+    engine.send(format!("go movetime {}", movetime_ms)).await?;
+
+    let bestmove = async {
+        let (mut nps, mut nodes, mut depth) = (0, 0, 0);
+        loop {
+            match rx.recv().await {
+                Ok(line) => {
+                    if let Some(stats) = line.starts_with("info").then(|| parse_info(&line, 0)).flatten() {
+                        nps = nps.max(stats.nps);
+                        nodes = nodes.max(stats.nodes);
+                        depth = depth.max(stats.depth);
+                    }
+                    if let Some(rest) = line.strip_prefix("bestmove") {
+                        let bestmove = rest.split_whitespace().next().filter(|mv| *mv != "(none)" && *mv != "0000").map(str::to_string);
+                        return Ok(SearchOutcome { bestmove, nps, nodes, depth });
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return Err(anyhow::anyhow!("Engine disconnected")),
+            }
+        }
+    };
+    // Latency grace on top, so very short movetimes don't time out on process overhead
+    let timeout_ms = movetime_timeout_ms(movetime_ms);
+    timeout(Duration::from_millis(timeout_ms), bestmove).await
+        .map_err(|_| anyhow::anyhow!("no bestmove within {} ms", timeout_ms))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn epd_line_reads_moves_as_uci_and_id() {
+        let position = parse_epd_line("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - bm Bb5 Bc4; am Nxe5; id \"test.001\";").unwrap();
+        assert_eq!(position.id.as_deref(), Some("test.001"));
+        assert_eq!(position.best_moves, vec!["f1b5", "f1c4"]);
+        assert_eq!(position.avoid_moves, vec!["f3e5"]);
+        assert!(position.is_solved_by("f1b5"));
+        assert!(!position.is_solved_by("d2d4"));
+    }
+
+    #[test]
+    fn avoid_move_positions_accept_anything_else() {
+        let position = parse_epd_line("4k3/8/8/8/8/8/4P3/4K3 w - - am Kd1;").unwrap();
+        assert!(position.is_solved_by("e2e4"));
+        assert!(!position.is_solved_by("e1d1"));
+        assert!(parse_epd_line("4k3/8/8/8/8/8/4P3/4K3 w - - id \"no moves\";").is_none());
+    }
+}
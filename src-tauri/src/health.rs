@@ -0,0 +1,105 @@
+use crate::types::{EngineHealthConfig, EngineStats};
+
+/// What one engine's searches looked like over a game, fed from its `info` lines.
+/// Symptoms only count once they last `config.moves` searches in a row, so a
+/// single odd move never warns.
+pub struct HealthMonitor {
+    config: EngineHealthConfig,
+    search: SearchSample,
+    zero_nodes: u32,
+    slow: u32,
+    hash_full: u32,
+}
+
+#[derive(Default)]
+struct SearchSample {
+    nodes: Option<u64>,
+    nps: Option<u64>,
+    hash_full: Option<u32>,
+}
+
+impl HealthMonitor {
+    pub fn new(config: EngineHealthConfig) -> Self {
+        Self { config, search: SearchSample::default(), zero_nodes: 0, slow: 0, hash_full: 0 }
+    }
+
+    /// `stats` was parsed from `line`; fields the line doesn't mention don't count
+    /// as zero.
+    pub fn record_info(&mut self, line: &str, stats: &EngineStats) {
+        let has = |token: &str| line.split_whitespace().any(|t| t == token);
+        if has("nodes") {
+            self.search.nodes = Some(self.search.nodes.unwrap_or(0).max(stats.nodes));
+        }
+        if has("nps") {
+            self.search.nps = Some(stats.nps);
+        }
+        if stats.hash_full.is_some() {
+            self.search.hash_full = stats.hash_full;
+        }
+    }
+
+    /// Closes the search on `bestmove` and returns the symptoms that have just
+    /// lasted long enough to report; each is reported once per streak.
+    pub fn finish_search(&mut self) -> Vec<String> {
+        let search = std::mem::take(&mut self.search);
+        let limit = self.config.moves;
+        if limit == 0 {
+            return Vec::new();
+        }
+        let streak = |count: &mut u32, active: bool| {
+            *count = if active { *count + 1 } else { 0 };
+            *count == limit
+        };
+
+        let mut symptoms = Vec::new();
+        if streak(&mut self.zero_nodes, search.nodes == Some(0)) {
+            symptoms.push(format!("searched 0 nodes for {} moves in a row", limit));
+        }
+        if streak(&mut self.slow, search.nps.is_some_and(|nps| nps < self.config.min_nps)) {
+            symptoms.push(format!("reported under {} nps for {} moves in a row; it may be hanging", self.config.min_nps, limit));
+        }
+        if streak(&mut self.hash_full, search.hash_full.is_some_and(|full| full >= self.config.hashfull_limit)) {
+            symptoms.push(format!("hash at least {} permille full for {} moves in a row; Hash may be too small", self.config.hashfull_limit, limit));
+        }
+        symptoms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbiter::parse_info;
+
+    fn search(monitor: &mut HealthMonitor, line: &str) -> Vec<String> {
+        monitor.record_info(line, &parse_info(line, 0).unwrap());
+        monitor.finish_search()
+    }
+
+    #[test]
+    fn symptoms_warn_once_when_they_last_long_enough() {
+        let mut monitor = HealthMonitor::new(config_with_moves(3));
+        let full = "info depth 20 nodes 5000000 nps 2000000 hashfull 1000 score cp 10 pv e2e4";
+        assert!(search(&mut monitor, full).is_empty());
+        assert!(search(&mut monitor, full).is_empty());
+        assert_eq!(search(&mut monitor, full).len(), 1);
+        assert!(search(&mut monitor, full).is_empty());
+
+        // A healthy search resets the streak
+        let mut monitor = HealthMonitor::new(config_with_moves(2));
+        let stalled = "info depth 1 nodes 0 nps 0 score cp 0 pv e2e4";
+        assert!(search(&mut monitor, stalled).is_empty());
+        assert!(search(&mut monitor, "info depth 12 nodes 90000 nps 900000 score cp 5 pv e2e4").is_empty());
+        assert!(search(&mut monitor, stalled).is_empty());
+        assert_eq!(search(&mut monitor, stalled).len(), 2);
+    }
+
+    #[test]
+    fn fields_an_engine_never_reports_do_not_warn() {
+        let mut monitor = HealthMonitor::new(config_with_moves(1));
+        assert!(search(&mut monitor, "info depth 8 score cp 30 pv e2e4").is_empty());
+    }
+
+    fn config_with_moves(moves: u32) -> EngineHealthConfig {
+        EngineHealthConfig { moves, ..EngineHealthConfig::default() }
+    }
+}
@@ -5,19 +5,24 @@ use std::path::{Path, PathBuf};
 use std::panic::AssertUnwindSafe;
 use std::sync::{Arc, Mutex};
 use futures::FutureExt;
-use tokio::sync::mpsc;
-use crate::arbiter::Arbiter;
-use crate::types::{TournamentConfig, GameUpdate, EngineStats, ScheduledGame, TournamentError, TournamentResumeState, UciOption};
-use crate::stats::TournamentStats;
+use crate::arbiter::{Arbiter, ArbiterChannels, ArbiterReceivers};
+use crate::types::{CompletionReason, EngineConfig, TournamentConfig, GameUpdate, ScheduledGame, Standings, TournamentResumeState, UciOption};
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
 pub mod arbiter;
+pub mod bench;
+pub mod diagnostics;
+pub mod eco;
+pub mod health;
+pub mod epd;
+pub mod pgn_filter;
 pub mod uci;
 pub mod types;
 pub mod stats;
 pub mod sprt;
 pub mod mock_engine;
+pub mod verify;
 
 struct AppState {
     current_arbiter: Arc<Mutex<Option<Arc<Arbiter>>>>,
@@ -27,51 +32,77 @@ struct AppState {
 #[derive(Default)]
 struct ProgressTracker {
     schedule_states: HashMap<usize, String>,
+    game_progress: HashMap<usize, f64>, // share of each Active game already played
+    shown: f64, // never goes back, even when games are rescheduled
 }
 
 impl ProgressTracker {
     fn reset(&mut self) {
         self.schedule_states.clear();
+        self.game_progress.clear();
+        self.shown = 0.0;
     }
 
-    fn apply_update(&mut self, update: &ScheduledGame) -> (u32, u32) {
+    fn apply_update(&mut self, update: &ScheduledGame) -> Option<f64> {
         if update.state == "Removed" {
             self.schedule_states.remove(&update.id);
         } else {
             self.schedule_states.insert(update.id, update.state.clone());
         }
-        self.counts()
+        if update.state != "Active" {
+            self.game_progress.remove(&update.id);
+        }
+        self.progress()
+    }
+
+    fn apply_game_progress(&mut self, game_id: usize, fraction: f64) -> Option<f64> {
+        if self.schedule_states.get(&game_id).map(String::as_str) != Some("Active") {
+            return None;
+        }
+        let entry = self.game_progress.entry(game_id).or_insert(0.0);
+        *entry = entry.max(fraction);
+        self.progress()
     }
 
-    fn counts(&self) -> (u32, u32) {
+    fn apply_game_update(&mut self, update: &GameUpdate) -> Option<f64> {
+        let plies = update.move_number.saturating_sub(1) as usize * 2;
+        self.apply_game_progress(update.game_id, arbiter::estimated_game_progress(plies))
+    }
+
+    /// Completed share of the schedule, counting Active games by how far along they are.
+    fn progress(&mut self) -> Option<f64> {
         let mut total_games = 0;
-        let mut remaining_games = 0;
-        for state in self.schedule_states.values() {
+        let mut completed = 0.0;
+        for (id, state) in &self.schedule_states {
             if state == "Removed" {
                 continue;
             }
             total_games += 1;
-            if !matches!(state.as_str(), "Finished" | "Aborted" | "Skipped") {
-                remaining_games += 1;
+            if matches!(state.as_str(), "Finished" | "Aborted" | "Skipped") {
+                completed += 1.0;
+            } else if state == "Active" {
+                completed += self.game_progress.get(id).copied().unwrap_or(0.0);
             }
         }
-        (total_games, remaining_games)
+        if total_games == 0 {
+            return None;
+        }
+        self.shown = self.shown.max(completed / total_games as f64);
+        Some(self.shown)
     }
 }
 
-fn update_taskbar_progress(app: &AppHandle, total_games: u32, remaining_games: u32) {
+fn update_taskbar_progress(app: &AppHandle, ratio: Option<f64>) {
     let Some(window) = app.get_webview_window("main") else {
         return;
     };
-    if total_games == 0 {
+    let Some(ratio) = ratio else {
         let _ = window.set_progress_bar(ProgressBarState {
             status: Some(ProgressBarStatus::None),
             progress: None,
         });
         return;
-    }
-    let completed = total_games.saturating_sub(remaining_games);
-    let ratio = completed as f64 / total_games as f64;
+    };
     let progress = (ratio * 100.0).round().clamp(0.0, 100.0) as u64;
     let _ = window.set_progress_bar(ProgressBarState {
         status: Some(ProgressBarStatus::Normal),
@@ -84,11 +115,25 @@ fn handle_schedule_progress_update(
     progress_tracker: &Arc<Mutex<ProgressTracker>>,
     update: &ScheduledGame,
 ) {
-    let (total_games, remaining_games) = {
+    let progress = {
         let mut tracker = progress_tracker.lock().unwrap_or_else(|e| e.into_inner());
         tracker.apply_update(update)
     };
-    update_taskbar_progress(app, total_games, remaining_games);
+    update_taskbar_progress(app, progress);
+}
+
+fn handle_game_progress_update(
+    app: &AppHandle,
+    progress_tracker: &Arc<Mutex<ProgressTracker>>,
+    update: &GameUpdate,
+) {
+    let progress = {
+        let mut tracker = progress_tracker.lock().unwrap_or_else(|e| e.into_inner());
+        tracker.apply_game_update(update)
+    };
+    if progress.is_some() {
+        update_taskbar_progress(app, progress);
+    }
 }
 
 fn resume_state_path(app: &AppHandle) -> Result<PathBuf, String> {
@@ -98,13 +143,19 @@ fn resume_state_path(app: &AppHandle) -> Result<PathBuf, String> {
 
 #[tauri::command]
 async fn start_match(app: AppHandle, state: State<'_, AppState>, mut config: TournamentConfig) -> Result<(), String> {
-    let trimmed_path = config.pgn_path.as_deref().map(str::trim).filter(|path| !path.is_empty());
-    config.pgn_path = Some(trimmed_path.unwrap_or("tournament.pgn").to_string());
+    let trimmed_path = config.pgn_path.as_deref().map(str::trim).filter(|path| !path.is_empty()).map(str::to_string);
+    // Per-game files alone are allowed; otherwise every run keeps a master PGN
+    config.pgn_path = trimmed_path.or_else(|| config.pgn_per_game_dir.is_none().then(|| "tournament.pgn".to_string()));
+    Arbiter::validate_games_count(&config).map_err(|e| format!("Cannot start: {}", e))?;
+    Arbiter::validate_engine_identities(&config).map_err(|e| format!("Cannot start: {}", e))?;
     for engine in &config.engines {
         let engine_path = Path::new(&engine.path);
         if !engine_path.exists() {
             return Err("Cannot start: engine path missing or not executable".to_string());
         }
+        if !engine_path.is_file() {
+            return Err("Cannot start: engine path is a directory, expected an executable".to_string());
+        }
         #[cfg(unix)]
         {
             let metadata = std::fs::metadata(engine_path)
@@ -114,24 +165,39 @@ async fn start_match(app: AppHandle, state: State<'_, AppState>, mut config: Tou
             }
         }
     }
+    Arbiter::validate_engine_capabilities(&config).await.map_err(|e| format!("Cannot start: {}", e))?;
     let maybe_arbiter = { let arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner()); arbiter_lock.clone() };
     if let Some(arbiter) = maybe_arbiter { arbiter.stop().await; }
     {
         let mut tracker = state.progress_tracker.lock().unwrap_or_else(|e| e.into_inner());
         tracker.reset();
     }
-    let (game_tx, mut game_rx) = mpsc::channel::<GameUpdate>(100);
-    let (stats_tx, mut stats_rx) = mpsc::channel::<EngineStats>(100);
-    let (tourney_stats_tx, mut tourney_stats_rx) = mpsc::channel::<TournamentStats>(100);
-    let (schedule_update_tx, mut schedule_update_rx) = mpsc::channel::<ScheduledGame>(100);
-    let (error_tx, mut error_rx) = mpsc::channel::<TournamentError>(100);
-
-    let arbiter = Arbiter::new(config, game_tx, stats_tx, tourney_stats_tx, schedule_update_tx, error_tx).await.map_err(|e| e.to_string())?;
+    let (channels, receivers) = ArbiterChannels::new(100);
+    let ArbiterReceivers {
+        game_update_rx: mut game_rx,
+        mut stats_rx,
+        mut tourney_stats_rx,
+        mut schedule_update_rx,
+        mut error_rx,
+        mut warning_rx,
+        mut game_completed_rx,
+        mut pairing_sprt_rx,
+        mut time_update_rx,
+        mut engine_log_rx,
+    } = receivers;
+
+    let arbiter = Arbiter::new(config, channels).await.map_err(|e| e.to_string())?;
     let arbiter = Arc::new(arbiter);
     { let mut arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner()); *arbiter_lock = Some(arbiter.clone()); }
 
     let app_handle = app.clone();
-    tokio::spawn(async move { while let Some(update) = game_rx.recv().await { let _ = app_handle.emit("game-update", update); } });
+    let progress_tracker_games = state.progress_tracker.clone();
+    tokio::spawn(async move {
+        while let Some(update) = game_rx.recv().await {
+            handle_game_progress_update(&app_handle, &progress_tracker_games, &update);
+            let _ = app_handle.emit("game-update", update);
+        }
+    });
 
     let app_handle_stats = app.clone();
     tokio::spawn(async move { while let Some(stats) = stats_rx.recv().await { let _ = app_handle_stats.emit("engine-stats", stats); } });
@@ -151,13 +217,32 @@ async fn start_match(app: AppHandle, state: State<'_, AppState>, mut config: Tou
     let app_handle_errors = app.clone();
     tokio::spawn(async move { while let Some(error) = error_rx.recv().await { let _ = app_handle_errors.emit("toast", error); } });
 
+    let app_handle_warnings = app.clone();
+    tokio::spawn(async move { while let Some(warning) = warning_rx.recv().await { let _ = app_handle_warnings.emit("tournament-warning", warning); } });
+
+    let app_handle_completed = app.clone();
+    tokio::spawn(async move { while let Some(completed) = game_completed_rx.recv().await { let _ = app_handle_completed.emit("game-completed", completed); } });
+
+    let app_handle_pairing_sprt = app.clone();
+    tokio::spawn(async move { while let Some(concluded) = pairing_sprt_rx.recv().await { let _ = app_handle_pairing_sprt.emit("pairing-sprt-concluded", concluded); } });
+
+    let app_handle_time = app.clone();
+    tokio::spawn(async move { while let Some(update) = time_update_rx.recv().await { let _ = app_handle_time.emit("time-update", update); } });
+
+    let app_handle_engine_log = app.clone();
+    tokio::spawn(async move { while let Some(line) = engine_log_rx.recv().await { let _ = app_handle_engine_log.emit("engine-log", line); } });
+
     let app_handle = app.clone();
     let arbiter_clone = arbiter.clone();
     tokio::spawn(async move {
+        let started = std::time::Instant::now();
         let result = AssertUnwindSafe(arbiter_clone.run_tournament()).catch_unwind().await;
-        match result {
-            Ok(Ok(())) => {}
-            Ok(Err(e)) => println!("Tournament error: {}", e),
+        let complete = match result {
+            Ok(Ok(complete)) => complete,
+            Ok(Err(e)) => {
+                tracing::error!("Tournament error: {}", e);
+                arbiter_clone.completion_summary(CompletionReason::Error, started.elapsed()).await
+            }
             Err(panic) => {
                 let panic_message = if let Some(message) = panic.downcast_ref::<&str>() {
                     (*message).to_string()
@@ -166,10 +251,12 @@ async fn start_match(app: AppHandle, state: State<'_, AppState>, mut config: Tou
                 } else {
                     "Unknown panic".to_string()
                 };
-                eprintln!("Tournament panic: {}", panic_message);
+                tracing::error!("Tournament panic: {}", panic_message);
                 let _ = app_handle.emit("critical-error", panic_message);
+                arbiter_clone.completion_summary(CompletionReason::Error, started.elapsed()).await
             }
-        }
+        };
+        let _ = app_handle.emit("tournament-complete", complete);
     });
     Ok(())
 }
@@ -213,19 +300,36 @@ async fn resume_match(app: AppHandle, state: State<'_, AppState>) -> Result<(),
         tracker.reset();
     }
 
-    let (game_tx, mut game_rx) = mpsc::channel::<GameUpdate>(100);
-    let (stats_tx, mut stats_rx) = mpsc::channel::<EngineStats>(100);
-    let (tourney_stats_tx, mut tourney_stats_rx) = mpsc::channel::<TournamentStats>(100);
-    let (schedule_update_tx, mut schedule_update_rx) = mpsc::channel::<ScheduledGame>(100);
-    let (error_tx, mut error_rx) = mpsc::channel::<TournamentError>(100);
-
-    let arbiter = Arbiter::new(config, game_tx, stats_tx, tourney_stats_tx, schedule_update_tx, error_tx).await.map_err(|e| e.to_string())?;
+    let (channels, receivers) = ArbiterChannels::new(100);
+    let ArbiterReceivers {
+        game_update_rx: mut game_rx,
+        mut stats_rx,
+        mut tourney_stats_rx,
+        mut schedule_update_rx,
+        mut error_rx,
+        mut warning_rx,
+        mut game_completed_rx,
+        mut pairing_sprt_rx,
+        mut time_update_rx,
+        mut engine_log_rx,
+    } = receivers;
+
+    let arbiter = Arbiter::new(config, channels).await.map_err(|e| e.to_string())?;
     arbiter.load_schedule_state(resume_state.schedule).await;
+    if resume_state.paused {
+        arbiter.set_paused(true).await;
+    }
     let arbiter = Arc::new(arbiter);
     { let mut arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner()); *arbiter_lock = Some(arbiter.clone()); }
 
     let app_handle = app.clone();
-    tokio::spawn(async move { while let Some(update) = game_rx.recv().await { let _ = app_handle.emit("game-update", update); } });
+    let progress_tracker_games = state.progress_tracker.clone();
+    tokio::spawn(async move {
+        while let Some(update) = game_rx.recv().await {
+            handle_game_progress_update(&app_handle, &progress_tracker_games, &update);
+            let _ = app_handle.emit("game-update", update);
+        }
+    });
 
     let app_handle_stats = app.clone();
     tokio::spawn(async move { while let Some(stats) = stats_rx.recv().await { let _ = app_handle_stats.emit("engine-stats", stats); } });
@@ -245,13 +349,32 @@ async fn resume_match(app: AppHandle, state: State<'_, AppState>) -> Result<(),
     let app_handle_errors = app.clone();
     tokio::spawn(async move { while let Some(error) = error_rx.recv().await { let _ = app_handle_errors.emit("toast", error); } });
 
+    let app_handle_warnings = app.clone();
+    tokio::spawn(async move { while let Some(warning) = warning_rx.recv().await { let _ = app_handle_warnings.emit("tournament-warning", warning); } });
+
+    let app_handle_completed = app.clone();
+    tokio::spawn(async move { while let Some(completed) = game_completed_rx.recv().await { let _ = app_handle_completed.emit("game-completed", completed); } });
+
+    let app_handle_pairing_sprt = app.clone();
+    tokio::spawn(async move { while let Some(concluded) = pairing_sprt_rx.recv().await { let _ = app_handle_pairing_sprt.emit("pairing-sprt-concluded", concluded); } });
+
+    let app_handle_time = app.clone();
+    tokio::spawn(async move { while let Some(update) = time_update_rx.recv().await { let _ = app_handle_time.emit("time-update", update); } });
+
+    let app_handle_engine_log = app.clone();
+    tokio::spawn(async move { while let Some(line) = engine_log_rx.recv().await { let _ = app_handle_engine_log.emit("engine-log", line); } });
+
     let app_handle = app.clone();
     let arbiter_clone = arbiter.clone();
     tokio::spawn(async move {
+        let started = std::time::Instant::now();
         let result = AssertUnwindSafe(arbiter_clone.run_tournament()).catch_unwind().await;
-        match result {
-            Ok(Ok(())) => {}
-            Ok(Err(e)) => println!("Tournament error: {}", e),
+        let complete = match result {
+            Ok(Ok(complete)) => complete,
+            Ok(Err(e)) => {
+                tracing::error!("Tournament error: {}", e);
+                arbiter_clone.completion_summary(CompletionReason::Error, started.elapsed()).await
+            }
             Err(panic) => {
                 let panic_message = if let Some(message) = panic.downcast_ref::<&str>() {
                     (*message).to_string()
@@ -260,10 +383,12 @@ async fn resume_match(app: AppHandle, state: State<'_, AppState>) -> Result<(),
                 } else {
                     "Unknown panic".to_string()
                 };
-                eprintln!("Tournament panic: {}", panic_message);
+                tracing::error!("Tournament panic: {}", panic_message);
                 let _ = app_handle.emit("critical-error", panic_message);
+                arbiter_clone.completion_summary(CompletionReason::Error, started.elapsed()).await
             }
-        }
+        };
+        let _ = app_handle.emit("tournament-complete", complete);
     });
     Ok(())
 }
@@ -282,6 +407,24 @@ async fn pause_match(state: State<'_, AppState>, paused: bool) -> Result<(), Str
     Ok(())
 }
 
+#[tauri::command]
+async fn abort_game(state: State<'_, AppState>, game_id: usize) -> Result<(), String> {
+    let maybe_arbiter = { let arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner()); arbiter_lock.clone() };
+    if let Some(arbiter) = maybe_arbiter {
+        arbiter.abort_game(game_id).await.map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn set_pgn_path(state: State<'_, AppState>, path: String, copy_existing: bool) -> Result<(), String> {
+    let maybe_arbiter = { let arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner()); arbiter_lock.clone() };
+    if let Some(arbiter) = maybe_arbiter {
+        arbiter.set_pgn_path(path, copy_existing).await.map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
 #[tauri::command]
 async fn update_remaining_rounds(state: State<'_, AppState>, remaining_rounds: u32) -> Result<(), String> {
     let maybe_arbiter = { let arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner()); arbiter_lock.clone() };
@@ -291,6 +434,24 @@ async fn update_remaining_rounds(state: State<'_, AppState>, remaining_rounds: u
     Ok(())
 }
 
+#[tauri::command]
+async fn prioritize_pairing(state: State<'_, AppState>, idx_a: usize, idx_b: usize) -> Result<usize, String> {
+    let maybe_arbiter = { let arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner()); arbiter_lock.clone() };
+    match maybe_arbiter {
+        Some(arbiter) => arbiter.prioritize_pairing(idx_a, idx_b).await.map_err(|e| e.to_string()),
+        None => Ok(0),
+    }
+}
+
+#[tauri::command]
+async fn cancel_pairing(state: State<'_, AppState>, idx_a: usize, idx_b: usize) -> Result<usize, String> {
+    let maybe_arbiter = { let arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner()); arbiter_lock.clone() };
+    match maybe_arbiter {
+        Some(arbiter) => arbiter.cancel_pairing(idx_a, idx_b).await.map_err(|e| e.to_string()),
+        None => Ok(0),
+    }
+}
+
 #[tauri::command]
 async fn set_disabled_engines(state: State<'_, AppState>, disabled_engine_ids: Vec<String>) -> Result<(), String> {
     let maybe_arbiter = { let arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner()); arbiter_lock.clone() };
@@ -298,6 +459,22 @@ async fn set_disabled_engines(state: State<'_, AppState>, disabled_engine_ids: V
     Ok(())
 }
 
+#[tauri::command]
+async fn reenable_engine(state: State<'_, AppState>, engine_id: String) -> Result<(), String> {
+    let maybe_arbiter = { let arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner()); arbiter_lock.clone() };
+    if let Some(arbiter) = maybe_arbiter { arbiter.reenable_engine(&engine_id).await; }
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_live_standings(state: State<'_, AppState>) -> Result<Standings, String> {
+    let maybe_arbiter = { let arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner()); arbiter_lock.clone() };
+    match maybe_arbiter {
+        Some(arbiter) => Ok(arbiter.live_standings().await),
+        None => Ok(Standings::default()),
+    }
+}
+
 #[tauri::command]
 async fn export_tournament_pgn(source_path: String, destination_path: String) -> Result<(), String> {
     let source = Path::new(&source_path);
@@ -318,11 +495,79 @@ async fn export_tournament_pgn(source_path: String, destination_path: String) ->
     Ok(())
 }
 
+/// Like `export_tournament_pgn`, keeping only the games `filter` matches; returns
+/// how many were written.
+#[tauri::command]
+async fn export_filtered_pgn(source_path: String, destination_path: String, filter: pgn_filter::PgnFilter) -> Result<usize, String> {
+    pgn_filter::export_filtered_pgn(&source_path, &destination_path, &filter).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn preview_schedule(config: TournamentConfig) -> Result<Vec<ScheduledGame>, String> {
+    Arbiter::preview_schedule(&config).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn export_opening_pins(state: State<'_, AppState>, destination_path: String) -> Result<(), String> {
+    let maybe_arbiter = { let arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner()); arbiter_lock.clone() };
+    let Some(arbiter) = maybe_arbiter else {
+        return Err("No tournament is running".to_string());
+    };
+    let pins = arbiter.opening_pins().await;
+    let json = serde_json::to_string_pretty(&pins).map_err(|e| e.to_string())?;
+    std::fs::write(&destination_path, json)
+        .map_err(|e| format!("Failed to write opening pins to {}: {}", destination_path, e))?;
+    Ok(())
+}
+
 #[tauri::command]
 async fn query_engine_options(path: String) -> Result<Vec<UciOption>, String> {
     uci::query_engine_options(&path).await.map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+async fn identify_engine(path: String) -> Result<uci::EngineInfo, String> {
+    uci::identify_engine(&path).await.map_err(|e| e.to_string())
+}
+
+/// Resolves on Ctrl-C, or SIGTERM on Unix, so a headless run can save its resume state.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    Ok(()) = tokio::signal::ctrl_c() => {}
+                    _ = sigterm.recv() => {}
+                }
+            }
+            Err(_) => {
+                if tokio::signal::ctrl_c().await.is_err() { std::future::pending::<()>().await }
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        if tokio::signal::ctrl_c().await.is_err() { std::future::pending::<()>().await }
+    }
+}
+
+#[tauri::command]
+async fn run_epd_suite(engine: EngineConfig, path: String, movetime_ms: u64) -> Result<epd::EpdReport, String> {
+    epd::run_epd_suite(&engine, &path, movetime_ms).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn verify_engine(path: String) -> Result<verify::EngineVerification, String> {
+    verify::verify_engine(&path).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn benchmark_engine(path: String, positions: Option<Vec<String>>, movetime_ms: u64) -> Result<bench::BenchResult, String> {
+    bench::benchmark_engine(&path, positions, movetime_ms).await.map_err(|e| e.to_string())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     env_logger::init();
@@ -334,6 +579,26 @@ pub fn run() {
             current_arbiter: Arc::new(Mutex::new(None)),
             progress_tracker: Arc::new(Mutex::new(ProgressTracker::default())),
         })
+        .setup(|app| {
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                shutdown_signal().await;
+                let state = app_handle.state::<AppState>();
+                let maybe_arbiter = {
+                    let mut arbiter_lock = state.current_arbiter.lock().unwrap_or_else(|e| e.into_inner());
+                    let arbiter = arbiter_lock.clone();
+                    *arbiter_lock = None;
+                    arbiter
+                };
+                if let Some(arbiter) = maybe_arbiter {
+                    if let Err(err) = arbiter.shutdown().await {
+                        tracing::error!("Failed to persist tournament state on shutdown: {}", err);
+                    }
+                }
+                app_handle.exit(0);
+            });
+            Ok(())
+        })
         .on_window_event(|window, event| {
             if matches!(event, tauri::WindowEvent::Destroyed) {
                 let state = window.state::<AppState>();
@@ -352,16 +617,78 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             start_match,
+            preview_schedule,
             stop_match,
             pause_match,
+            abort_game,
+            set_pgn_path,
             update_remaining_rounds,
+            prioritize_pairing,
+            cancel_pairing,
             set_disabled_engines,
+            reenable_engine,
+            get_live_standings,
             get_saved_tournament,
             discard_saved_tournament,
             resume_match,
             export_tournament_pgn,
-            query_engine_options
+            export_filtered_pgn,
+            export_opening_pins,
+            query_engine_options,
+            identify_engine,
+            run_epd_suite,
+            verify_engine,
+            benchmark_engine
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduled(id: usize, state: &str) -> ScheduledGame {
+        ScheduledGame {
+            id,
+            white_name: "A".into(),
+            black_name: "B".into(),
+            state: state.into(),
+            result: None,
+            start_fen: None,
+            opening_index: None,
+            opening: None,
+        }
+    }
+
+    fn moved(game_id: usize, move_number: u32) -> GameUpdate {
+        GameUpdate {
+            fen: String::new(), last_move: None, white_time: 0, black_time: 0,
+            move_number, result: None, white_engine_idx: 0, black_engine_idx: 1,
+            game_id, termination: None, end_reason: None, pv_moves: None, start_fen: None,
+        }
+    }
+
+    #[test]
+    fn progress_never_goes_back_on_out_of_order_updates() {
+        let mut tracker = ProgressTracker::default();
+        tracker.apply_update(&scheduled(1, "Pending"));
+        tracker.apply_update(&scheduled(2, "Pending"));
+        // A move that overtakes its game's Active update is not counted yet
+        assert_eq!(tracker.apply_game_update(&moved(1, 20)), None);
+
+        let shown: Vec<f64> = [
+            tracker.apply_update(&scheduled(1, "Active")),
+            tracker.apply_game_update(&moved(1, 20)),
+            tracker.apply_game_update(&moved(1, 5)), // older than the one before
+            tracker.apply_update(&scheduled(3, "Pending")), // more games to play
+            tracker.apply_update(&scheduled(1, "Pending")), // rescheduled
+            tracker.apply_update(&scheduled(1, "Finished")),
+            tracker.apply_update(&scheduled(2, "Removed")),
+        ].into_iter().map(Option::unwrap).collect();
+
+        assert!(shown.windows(2).all(|pair| pair[0] <= pair[1]), "{:?}", shown);
+        assert!(shown[1] > shown[0]);
+        assert_eq!(shown[6], 0.5);
+    }
+}
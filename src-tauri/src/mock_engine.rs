@@ -1,56 +1,156 @@
+// lib.rs also declares this file as a module, where nothing calls `main`
+#![allow(dead_code)]
+
+use rand::Rng;
+use shakmaty::fen::Fen;
+use shakmaty::uci::Uci;
+use shakmaty::{CastlingMode, Chess, Position};
 use std::io::{self, BufRead, Write};
 use std::thread;
 use std::time::Duration;
 
+// Behaviour is picked through the environment so integration tests can wrap the
+// binary in a one-line script:
+//   MOCK_ENGINE_MODE        legal (default), random, draw, resign or e2e4
+//   MOCK_ENGINE_CRASH_AFTER think, then exit without a bestmove, on the `go` after N moves
+//   MOCK_ENGINE_THINK_MS    time spent on every `go`, 1000 by default
+//   MOCK_ENGINE_BANNER      print a banner on launch and chatter among the
+//                           options, like engines that load a network
+//   MOCK_ENGINE_OPTIONS     comma-separated check options to declare, e.g. UCI_Chess960
+//   MOCK_ENGINE_SILENT      stay up but never answer anything, like a hung engine
+//   MOCK_ENGINE_MATE        report `score mate N` (from the side to move) instead of a cp score
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    /// First legal move in generation order
+    Legal,
+    Random,
+    /// Legal moves with a 0 cp score, for draw adjudication
+    Draw,
+    /// Legal moves with a hopeless score, for resign adjudication
+    Resign,
+    /// Always `bestmove e2e4`, legal or not
+    E2e4,
+}
+
+impl Mode {
+    fn from_env() -> Self {
+        match std::env::var("MOCK_ENGINE_MODE").as_deref() {
+            Ok("random") => Mode::Random,
+            Ok("draw") => Mode::Draw,
+            Ok("resign") => Mode::Resign,
+            Ok("e2e4") => Mode::E2e4,
+            _ => Mode::Legal,
+        }
+    }
+
+    fn scores(self) -> [i32; 2] {
+        match self {
+            Mode::Draw => [0, 0],
+            Mode::Resign => [-1000, -1000],
+            _ => [20, 25],
+        }
+    }
+}
+
+fn env_number(name: &str) -> Option<u64> {
+    std::env::var(name).ok()?.parse().ok()
+}
+
+/// `position startpos moves ...` or `position fen <fen> moves ...`. Moves that
+/// don't apply are dropped along with everything after them.
+fn parse_position(parts: &[&str]) -> Chess {
+    let moves_at = parts.iter().position(|part| *part == "moves").unwrap_or(parts.len());
+    let mut pos = match parts.get(1) {
+        Some(&"fen") => {
+            let fen = parts[2..moves_at].join(" ");
+            Fen::from_ascii(fen.as_bytes()).ok()
+                .and_then(|setup| setup.into_position(CastlingMode::Standard).ok())
+                .unwrap_or_default()
+        }
+        _ => Chess::default(),
+    };
+    for mv in parts.iter().skip(moves_at + 1) {
+        let Some(m) = Uci::from_ascii(mv.as_bytes()).ok().and_then(|uci| uci.to_move(&pos).ok()) else { break };
+        pos.play_unchecked(&m);
+    }
+    pos
+}
+
+fn choose_move(pos: &Chess, mode: Mode) -> String {
+    if mode == Mode::E2e4 {
+        return "e2e4".to_string();
+    }
+    let moves = pos.legal_moves();
+    let chosen = match mode {
+        Mode::Random if !moves.is_empty() => moves.get(rand::rng().random_range(0..moves.len())),
+        _ => moves.first(),
+    };
+    chosen.map_or_else(|| "0000".to_string(), |m| m.to_uci(CastlingMode::Standard).to_string())
+}
+
 fn main() {
     let stdin = io::stdin();
     let mut stdout = io::stdout();
+    let mode = Mode::from_env();
+    let crash_after = env_number("MOCK_ENGINE_CRASH_AFTER");
+    let think = Duration::from_millis(env_number("MOCK_ENGINE_THINK_MS").unwrap_or(1000));
+    let banner = std::env::var("MOCK_ENGINE_BANNER").is_ok();
+    let declared_options = std::env::var("MOCK_ENGINE_OPTIONS").unwrap_or_default();
+    let silent = std::env::var("MOCK_ENGINE_SILENT").is_ok();
+    let mate: Option<i32> = std::env::var("MOCK_ENGINE_MATE").ok().and_then(|mate| mate.parse().ok());
+    if banner {
+        println!("MockEngine 1.0 by Jules");
+        println!("id name MockEngine banner");
+        println!("option name Banner type check default false");
+        println!("uciok");
+        stdout.flush().unwrap();
+    }
+    let mut pos = Chess::default();
+    let mut moves_played = 0;
 
     for line in stdin.lock().lines() {
         if let Ok(cmd) = line {
             let parts: Vec<&str> = cmd.split_whitespace().collect();
-            if parts.is_empty() { continue; }
+            if parts.is_empty() || silent { continue; }
 
             match parts[0] {
                 "uci" => {
                     println!("id name MockEngine 1.0");
                     println!("id author Jules");
+                    if banner {
+                        println!("Loading network mock.nnue");
+                        println!("option name Hash type spin default 16 min 1 max 1024");
+                        println!("info string network loaded");
+                        println!("option name Threads type spin default 1 min 1 max 64");
+                    }
+                    for name in declared_options.split(',').filter(|name| !name.is_empty()) {
+                        println!("option name {} type check default false", name);
+                    }
                     println!("uciok");
                 },
                 "isready" => println!("readyok"),
-                "ucinewgame" => {
-                    // Reset game state if we were tracking it
-                },
-                "position" => {
-                    // We don't track position in this simple mock
-                },
+                "ucinewgame" => pos = Chess::default(),
+                "position" => pos = parse_position(&parts),
                 "go" => {
-                    // simulate thinking
-                    // Send some info
-                    println!("info depth 1 score cp 20 nodes 100 pv e2e4");
-                    thread::sleep(Duration::from_millis(500));
-                    println!("info depth 2 score cp 25 nodes 200 pv e2e4");
-                    thread::sleep(Duration::from_millis(500));
-
-                    // Always return a valid move if possible, or just e2e4/e7e5 if startpos.
-                    // But if the arbiter sends a position where e2e4 is illegal, this mock will crash the arbiter or cause illegal move.
-                    // The arbiter logic checks legality.
-                    // For "startpos", e2e4 is valid for white.
-                    // For "startpos moves e2e4", black to move. e7e5 is valid.
-                    // To be smarter without a chess library, we can check the 'position' command string.
-
-                    let mut is_black = false;
-                    // Check if 'position' command was sent previously? No, we need to store state.
-                    // But here we process line by line.
-                    // Actually, 'go' comes after 'position'.
-                    // Let's just alternate or random for now, or just e2e4 if we assume we are white.
-                    // A true mock needs to be smarter or we only test white.
-                    // Let's try to be slightly smarter by checking if "moves" contains "e2e4".
-                    // But we don't have access to the previous position command here easily unless we store it.
-
-                    // For the purpose of "Verification Strategy", the user asked for "replies id name MockEngine and bestmove e2e4".
-                    // I will stick to that strictly as requested.
-                    println!("bestmove e2e4");
+                    if crash_after.is_some_and(|limit| moves_played >= limit) {
+                        thread::sleep(think);
+                        std::process::exit(1);
+                    }
+                    // simulate thinking, with the chosen move as the PV
+                    let bestmove = choose_move(&pos, mode);
+                    let [first, second] = mode.scores().map(|cp| match mate {
+                        Some(mate) => format!("mate {}", mate),
+                        None => format!("cp {}", cp),
+                    });
+                    println!("info string mock engine searching {} legal moves", pos.legal_moves().len());
+                    println!("info depth 1 score {} nodes 100 pv {}", first, bestmove);
+                    thread::sleep(think / 2);
+                    println!("info depth 2 score {} nodes 200 pv {}", second, bestmove);
+                    thread::sleep(think / 2);
+
+                    println!("bestmove {}", bestmove);
+                    moves_played += 1;
                 },
                 "quit" => break,
                 _ => {}
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Which games `export_filtered_pgn` keeps. Unset fields match every game.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PgnFilter {
+    #[serde(default)]
+    pub engine: Option<String>, // as White or Black
+    #[serde(default)]
+    pub decisive_only: bool,
+    #[serde(default)]
+    pub min_round: Option<usize>,
+    #[serde(default)]
+    pub max_round: Option<usize>,
+}
+
+impl PgnFilter {
+    pub fn matches(&self, game: &str) -> bool {
+        if let Some(engine) = self.engine.as_deref() {
+            if tag_value(game, "White") != Some(engine) && tag_value(game, "Black") != Some(engine) {
+                return false;
+            }
+        }
+        if self.decisive_only && !matches!(tag_value(game, "Result"), Some("1-0") | Some("0-1")) {
+            return false;
+        }
+        if self.min_round.is_some() || self.max_round.is_some() {
+            let Some(round) = tag_value(game, "Round").and_then(|round| round.parse::<usize>().ok()) else { return false };
+            if self.min_round.is_some_and(|min| round < min) || self.max_round.is_some_and(|max| round > max) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Splits a PGN file into its games, each with its tags and movetext. A tag line
+/// after movetext starts the next game, so a missing `[Event]` doesn't merge games.
+pub fn split_pgn_games(data: &str) -> Vec<&str> {
+    let mut games = Vec::new();
+    let (mut start, mut offset) = (0, 0);
+    let mut in_movetext = false;
+    for line in data.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            if in_movetext {
+                games.push(&data[start..offset]);
+                start = offset;
+                in_movetext = false;
+            }
+        } else if !trimmed.is_empty() {
+            in_movetext = true;
+        }
+        offset += line.len();
+    }
+    if !data[start..].trim().is_empty() {
+        games.push(&data[start..]);
+    }
+    games
+}
+
+fn tag_value<'a>(game: &'a str, name: &str) -> Option<&'a str> {
+    game.lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || line.starts_with('['))
+        .find_map(|line| line.strip_prefix('[')?.strip_prefix(name)?.strip_prefix(" \"")?.strip_suffix("\"]"))
+}
+
+/// The games of `data` that `filter` keeps, each followed by a blank line.
+pub fn filter_pgn(data: &str, filter: &PgnFilter) -> (String, usize) {
+    let mut kept = String::new();
+    let mut count = 0;
+    for game in split_pgn_games(data).into_iter().filter(|game| filter.matches(game)) {
+        kept.push_str(game.trim());
+        kept.push_str("\n\n");
+        count += 1;
+    }
+    (kept, count)
+}
+
+/// Writes the games of `source` matching `filter` to `destination` and returns
+/// how many there were.
+pub fn export_filtered_pgn(source: &str, destination: &str, filter: &PgnFilter) -> anyhow::Result<usize> {
+    let data = std::fs::read_to_string(source).map_err(|e| anyhow::anyhow!("Failed to read PGN {}: {}", source, e))?;
+    let (kept, count) = filter_pgn(&data, filter);
+    if let Some(parent) = Path::new(destination).parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| anyhow::anyhow!("Failed to create destination directory {}: {}", parent.display(), e))?;
+    }
+    std::fs::write(destination, kept).map_err(|e| anyhow::anyhow!("Failed to write PGN to {}: {}", destination, e))?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THREE_GAMES: &str = "[Event \"T\"]\n[Round \"1\"]\n[White \"A\"]\n[Black \"B\"]\n[Result \"1-0\"]\n\n1. e2e4 e7e5 1-0\n\n\
+                               [Event \"T\"]\n[Round \"2\"]\n[White \"B\"]\n[Black \"C\"]\n[Result \"1/2-1/2\"]\n\n1. d2d4 d7d5 1/2-1/2\n\n\
+                               [Round \"3\"]\n[White \"C\"]\n[Black \"A\"]\n[Result \"0-1\"]\n\n1. c2c4 {A comment [with brackets]} 0-1\n";
+
+    #[test]
+    fn decisive_filter_keeps_only_won_games() {
+        assert_eq!(split_pgn_games(THREE_GAMES).len(), 3);
+        let (kept, count) = filter_pgn(THREE_GAMES, &PgnFilter { decisive_only: true, ..PgnFilter::default() });
+        assert_eq!(count, 2);
+        let rounds: Vec<Option<&str>> = split_pgn_games(&kept).into_iter().map(|game| tag_value(game, "Round")).collect();
+        assert_eq!(rounds, vec![Some("1"), Some("3")]);
+    }
+
+    #[test]
+    fn engine_and_round_filters_combine() {
+        let filter = PgnFilter { engine: Some("A".to_string()), min_round: Some(2), ..PgnFilter::default() };
+        let (kept, count) = filter_pgn(THREE_GAMES, &filter);
+        assert_eq!(count, 1);
+        assert!(kept.starts_with("[Round \"3\"]"));
+        assert_eq!(filter_pgn(THREE_GAMES, &PgnFilter::default()).1, 3);
+    }
+}
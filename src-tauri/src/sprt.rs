@@ -7,6 +7,18 @@ pub enum GameResult {
     Loss,
 }
 
+impl GameResult {
+    /// Maps a PGN result ("1-0", "0-1", "1/2-1/2") to the outcome for engine A.
+    pub fn from_result_str(result: &str, is_white_engine_a: bool) -> Option<Self> {
+        match result {
+            "1-0" => Some(if is_white_engine_a { GameResult::Win } else { GameResult::Loss }),
+            "0-1" => Some(if is_white_engine_a { GameResult::Loss } else { GameResult::Win }),
+            "1/2-1/2" => Some(GameResult::Draw),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum SprtState {
     Continue,
@@ -80,6 +92,10 @@ impl Sprt {
         }
     }
 
+    pub fn config(&self) -> &SprtConfig {
+        &self.config
+    }
+
     pub fn update_sprt(&mut self, result: GameResult) -> SprtStatus {
         match result {
             GameResult::Win => self.wins += 1,
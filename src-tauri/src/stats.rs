@@ -1,8 +1,23 @@
 use serde::{Deserialize, Serialize};
 use crate::sprt::{GameResult, Sprt, SprtConfig, SprtStatus};
-use crate::types::{Standings, StandingsEntry};
+use crate::types::{EngineFailureCounts, EngineSearchStats, HeadToHead, ScheduledGame, Standings, StandingsEntry, TournamentConfig};
 use std::collections::HashMap;
 
+/// Head-to-head result of one full run when the event is repeated.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RepetitionSummary {
+    pub repetition: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub elo_diff: f64,
+    pub error_margin: f64,
+}
+
+/// W/L/D, Elo and SPRT are aggregated from the point of view of each pairing's
+/// first member (`idx_a`): the first engine in Match mode, the seed in a
+/// gauntlet, and the lower-indexed engine of each round-robin pair.
+/// Per-engine results live in `standings`.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TournamentStats {
     pub wins: u32,
@@ -18,10 +33,23 @@ pub struct TournamentStats {
     pub sprt_state: String,
     pub sprt_enabled: bool,
     pub standings: Standings, // Integrated Standings
+    pub avg_plies: f64,
+    pub min_plies: Option<u32>,
+    pub max_plies: Option<u32>,
+    pub repetition: u32,
+    pub repetition_summaries: Vec<RepetitionSummary>,
+    pub repetition_elo_mean: f64,
+    pub repetition_elo_stddev: f64,
+    pub engine_search: HashMap<String, EngineSearchStats>, // engine name -> totals over all games
+    pub engine_failures: HashMap<String, EngineFailureCounts>, // engine name -> crashes/timeouts
+    #[serde(default)]
+    pub head_to_head: Vec<HeadToHead>, // crosstable rows, both directions of every pairing
     #[serde(skip)]
     sprt: Sprt,
     #[serde(skip)]
-    match_matrix: HashMap<(String, String), (f64, f64)>, // (P1, P2) -> (Score1, Score2) for SB calc
+    total_plies: u64,
+    #[serde(skip)]
+    length_samples: u32, // played games only; forfeits have no length
 }
 
 impl Default for TournamentStats {
@@ -43,7 +71,18 @@ impl Default for TournamentStats {
             sprt_enabled: true,
             sprt,
             standings: Standings::default(),
-            match_matrix: HashMap::new(),
+            avg_plies: 0.0,
+            min_plies: None,
+            max_plies: None,
+            total_plies: 0,
+            length_samples: 0,
+            repetition: 1,
+            repetition_summaries: Vec::new(),
+            repetition_elo_mean: 0.0,
+            repetition_elo_stddev: 0.0,
+            engine_search: HashMap::new(),
+            engine_failures: HashMap::new(),
+            head_to_head: Vec::new(),
         }
     }
 }
@@ -67,7 +106,18 @@ impl TournamentStats {
             sprt_enabled,
             sprt,
             standings: Standings::default(),
-            match_matrix: HashMap::new(),
+            avg_plies: 0.0,
+            min_plies: None,
+            max_plies: None,
+            total_plies: 0,
+            length_samples: 0,
+            repetition: 1,
+            repetition_summaries: Vec::new(),
+            repetition_elo_mean: 0.0,
+            repetition_elo_stddev: 0.0,
+            engine_search: HashMap::new(),
+            engine_failures: HashMap::new(),
+            head_to_head: Vec::new(),
         };
 
         if !sprt_enabled {
@@ -81,16 +131,10 @@ impl TournamentStats {
         stats
     }
 
+    /// `is_white_engine_a` is true when White is the pairing's `idx_a` engine.
     pub fn update(&mut self, result: &str, is_white_engine_a: bool) {
         // Result string is "1-0", "0-1", "1/2-1/2"
-        let game_result = match result {
-            "1-0" => Some(if is_white_engine_a { GameResult::Win } else { GameResult::Loss }),
-            "0-1" => Some(if is_white_engine_a { GameResult::Loss } else { GameResult::Win }),
-            "1/2-1/2" => Some(GameResult::Draw),
-            _ => None,
-        };
-
-        let Some(game_result) = game_result else {
+        let Some(game_result) = GameResult::from_result_str(result, is_white_engine_a) else {
             return;
         };
 
@@ -124,10 +168,73 @@ impl TournamentStats {
         // I will upgrade it to be generic for all modes by using `standings`.
     }
 
+    pub fn record_game_length(&mut self, plies: u32) {
+        self.total_plies += plies as u64;
+        self.length_samples += 1;
+        self.avg_plies = self.total_plies as f64 / self.length_samples as f64;
+        self.min_plies = Some(self.min_plies.map_or(plies, |min| min.min(plies)));
+        self.max_plies = Some(self.max_plies.map_or(plies, |max| max.max(plies)));
+    }
+
+    /// Snapshots the current repetition's head-to-head result and refreshes the
+    /// spread of Elo estimates across repetitions.
+    pub fn finish_repetition(&mut self) {
+        self.repetition_summaries.push(RepetitionSummary {
+            repetition: self.repetition,
+            wins: self.wins,
+            losses: self.losses,
+            draws: self.draws,
+            elo_diff: self.elo_diff,
+            error_margin: self.error_margin,
+        });
+        let n = self.repetition_summaries.len() as f64;
+        let mean = self.repetition_summaries.iter().map(|r| r.elo_diff).sum::<f64>() / n;
+        let variance = if n > 1.0 {
+            self.repetition_summaries.iter().map(|r| (r.elo_diff - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        self.repetition_elo_mean = mean;
+        self.repetition_elo_stddev = variance.sqrt();
+    }
+
+    /// Clears the head-to-head counters and SPRT for the next repetition.
+    /// Standings and game-length stats keep accumulating over the whole run.
+    pub fn start_repetition(&mut self) {
+        self.repetition += 1;
+        self.wins = 0;
+        self.losses = 0;
+        self.draws = 0;
+        self.total_games = 0;
+        self.elo_diff = 0.0;
+        self.error_margin = 0.0;
+        self.sprt = Sprt::new(self.sprt.config().clone());
+        if self.sprt_enabled {
+            let status = self.sprt.status();
+            self.apply_sprt_status(status);
+        }
+    }
+
+    pub fn record_engine_search(&mut self, engine_name: &str, search: &EngineSearchStats) {
+        self.engine_search.entry(engine_name.to_string()).or_default().merge(search);
+    }
+
+    pub fn record_crash(&mut self, engine_name: &str) {
+        self.engine_failures.entry(engine_name.to_string()).or_default().crashes += 1;
+    }
+
+    pub fn record_timeout(&mut self, engine_name: &str) {
+        self.engine_failures.entry(engine_name.to_string()).or_default().timeouts += 1;
+    }
+
     pub fn update_standings(&mut self, entries: Vec<StandingsEntry>) {
         self.standings.entries = entries;
     }
 
+    pub fn update_head_to_head(&mut self, rows: Vec<HeadToHead>) {
+        self.head_to_head = rows;
+    }
+
     fn calculate_elo(&mut self) {
         if self.total_games == 0 { return; }
         let score = self.wins as f64 + (self.draws as f64 * 0.5);
@@ -154,73 +261,200 @@ impl TournamentStats {
     }
 }
 
-pub fn calculate_standings(schedule: &[crate::types::ScheduledGame], engines: &[crate::types::EngineConfig]) -> Vec<StandingsEntry> {
-    let mut entries_map: HashMap<String, StandingsEntry> = HashMap::new();
-    let mut sb_map: HashMap<String, HashMap<String, f64>> = HashMap::new(); // Player -> Opponent -> Points Won Against
+/// Final summary written to `results_path`, meant to be parsed by CI.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TournamentResults {
+    pub completed: bool, // false when the run was stopped early
+    pub standings: Vec<StandingsEntry>,
+    pub head_to_head: Vec<HeadToHead>,
+    pub stats: TournamentStats, // Elo +/- margin and SPRT verdict from `idx_a`'s side
+    pub config: TournamentConfig,
+}
 
-    // Initialize entries
-    for engine in engines {
-        entries_map.insert(engine.name.clone(), StandingsEntry {
-            rank: 0,
-            engine_name: engine.name.clone(),
-            engine_id: engine.id.clone(),
-            games_played: 0,
-            points: 0.0,
-            score_percent: 0.0,
+impl TournamentResults {
+    pub fn new(schedule: &[ScheduledGame], stats: &TournamentStats, config: &TournamentConfig, completed: bool) -> Self {
+        let mut standings = calculate_standings(schedule, &config.engines, config.tiebreak_order.as_deref());
+        for entry in &mut standings {
+            if let Some(failures) = stats.engine_failures.get(&entry.engine_name) {
+                entry.crashes = failures.crashes;
+            }
+        }
+        Self {
+            completed,
+            standings,
+            head_to_head: head_to_head(schedule),
+            stats: stats.clone(),
+            config: config.clone(),
+        }
+    }
+}
+
+/// (White points, Black points) for a finished game; `None` for unfinished ones.
+fn result_points(result: &str) -> Option<(f64, f64)> {
+    match result {
+        "1-0" | "1-0 (forfeit)" => Some((1.0, 0.0)),
+        "0-1" | "0-1 (forfeit)" => Some((0.0, 1.0)),
+        "1/2-1/2" | "1/2-1/2 (forfeit)" => Some((0.5, 0.5)),
+        _ => None,
+    }
+}
+
+/// One row per (engine, opponent) that met, sorted by engine then opponent.
+pub fn head_to_head(schedule: &[ScheduledGame]) -> Vec<HeadToHead> {
+    let mut rows: HashMap<(String, String), HeadToHead> = HashMap::new();
+    for game in schedule {
+        let Some((w_pts, b_pts)) = game.result.as_deref().and_then(result_points) else { continue };
+        record_head_to_head(&mut rows, &game.white_name, &game.black_name, w_pts, b_pts);
+    }
+    sorted_head_to_head(rows.into_values().collect())
+}
+
+fn record_head_to_head(rows: &mut HashMap<(String, String), HeadToHead>, white: &str, black: &str, w_pts: f64, b_pts: f64) {
+    for (engine, opponent, points) in [(white, black, w_pts), (black, white, b_pts)] {
+        let row = rows.entry((engine.to_string(), opponent.to_string())).or_insert_with(|| HeadToHead {
+            engine: engine.to_string(),
+            opponent: opponent.to_string(),
             wins: 0,
             losses: 0,
             draws: 0,
-            crashes: 0, // Need to pipe this in if possible, or accept 0 for now
-            sb: 0.0,
-            elo: 0.0, // Need global ELO calc logic or placeholder
-            elo_diff: None,
+            points: 0.0,
         });
+        row.points += points;
+        if points == 1.0 { row.wins += 1; }
+        else if points == 0.5 { row.draws += 1; }
+        else { row.losses += 1; }
+    }
+}
+
+fn sorted_head_to_head(mut rows: Vec<HeadToHead>) -> Vec<HeadToHead> {
+    rows.sort_by(|a, b| a.engine.cmp(&b.engine).then_with(|| a.opponent.cmp(&b.opponent)));
+    rows
+}
+
+/// Criteria that order engines level on points, each applied to whoever is
+/// still level after the ones before it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tiebreak {
+    SonnebornBerger,
+    /// Points scored in the games among the engines still level.
+    DirectEncounter,
+    Wins,
+}
+
+/// Used when `tiebreak_order` is unset.
+const DEFAULT_TIEBREAKS: [Tiebreak; 2] = [Tiebreak::SonnebornBerger, Tiebreak::Wins];
+
+impl Tiebreak {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "sb" | "sonneborn_berger" => Some(Self::SonnebornBerger),
+            "direct_encounter" => Some(Self::DirectEncounter),
+            "wins" => Some(Self::Wins),
+            _ => None,
+        }
     }
 
+    fn key(self, entry: &StandingsEntry, level: &[StandingsEntry], points_won: &HashMap<String, HashMap<String, f64>>) -> f64 {
+        match self {
+            Self::SonnebornBerger => entry.sb,
+            Self::Wins => entry.wins as f64,
+            Self::DirectEncounter => {
+                let against = points_won.get(&entry.engine_name);
+                level.iter()
+                    .filter(|other| other.engine_name != entry.engine_name)
+                    .filter_map(|other| against?.get(&other.engine_name))
+                    .sum()
+            }
+        }
+    }
+}
+
+/// Orders `level` (engines equal so far) by the first tie-break, then each group
+/// still equal on it by the rest.
+fn break_ties(level: &mut [StandingsEntry], tiebreaks: &[Tiebreak], points_won: &HashMap<String, HashMap<String, f64>>) {
+    let Some((tiebreak, rest)) = tiebreaks.split_first() else { return };
+    if level.len() < 2 {
+        return;
+    }
+    let keys: HashMap<String, f64> = level.iter()
+        .map(|entry| (entry.engine_name.clone(), tiebreak.key(entry, level, points_won)))
+        .collect();
+    level.sort_by(|a, b| keys[&b.engine_name].partial_cmp(&keys[&a.engine_name]).unwrap_or(std::cmp::Ordering::Equal));
+    for group in level.chunk_by_mut(|a, b| keys[&a.engine_name] == keys[&b.engine_name]) {
+        break_ties(group, rest, points_won);
+    }
+}
+
+/// Rows are keyed by engine name, which the arbiter keeps unique
+/// (`Arbiter::validate_engine_identities`). Engines level on points are ordered by
+/// `tiebreak_order` (see `Tiebreak::parse`); unknown names are skipped.
+///
+/// Rebuilds everything from the schedule; `StandingsTracker` keeps the same
+/// numbers up to date one game at a time.
+pub fn calculate_standings(schedule: &[ScheduledGame], engines: &[crate::types::EngineConfig], tiebreak_order: Option<&[String]>) -> Vec<StandingsEntry> {
+    let mut entries_map = new_entries(engines);
+    let mut sb_map: HashMap<String, HashMap<String, f64>> = HashMap::new(); // Player -> Opponent -> Points Won Against
+
     // Process games for Points and Basic Stats
     for game in schedule {
         if let Some(result) = &game.result {
-            let white = &game.white_name;
-            let black = &game.black_name;
-
-            // Check if engines exist in map (might be disabled/removed ones, but typically they are in config)
-            if !entries_map.contains_key(white) { continue; } // Should not happen if config syncs
-            if !entries_map.contains_key(black) { continue; }
-
-            let (w_pts, b_pts) = match result.as_str() {
-                "1-0" | "1-0 (forfeit)" => (1.0, 0.0),
-                "0-1" | "0-1 (forfeit)" => (0.0, 1.0),
-                "1/2-1/2" | "1/2-1/2 (forfeit)" => (0.5, 0.5),
-                _ => (0.0, 0.0), // Unknown result
-            };
-
-            if let Some(entry) = entries_map.get_mut(white) {
-                entry.games_played += 1;
-                entry.points += w_pts;
-                if w_pts == 1.0 { entry.wins += 1; }
-                else if w_pts == 0.5 { entry.draws += 1; }
-                else { entry.losses += 1; }
-            }
-            if let Some(entry) = entries_map.get_mut(black) {
-                entry.games_played += 1;
-                entry.points += b_pts;
-                if b_pts == 1.0 { entry.wins += 1; }
-                else if b_pts == 0.5 { entry.draws += 1; }
-                else { entry.losses += 1; }
-            }
+            record_game(&mut entries_map, &mut sb_map, &game.white_name, &game.black_name, result);
+        }
+    }
+
+    rank_standings(entries_map, &sb_map, tiebreak_order)
+}
 
-            // Track H2H points for SB
-            *sb_map.entry(white.clone()).or_default().entry(black.clone()).or_insert(0.0) += w_pts;
-            *sb_map.entry(black.clone()).or_default().entry(white.clone()).or_insert(0.0) += b_pts;
+fn new_entries(engines: &[crate::types::EngineConfig]) -> HashMap<String, StandingsEntry> {
+    engines.iter().map(|engine| (engine.name.clone(), StandingsEntry {
+        rank: 0,
+        engine_name: engine.name.clone(),
+        engine_id: engine.id.clone(),
+        games_played: 0,
+        points: 0.0,
+        score_percent: 0.0,
+        wins: 0,
+        losses: 0,
+        draws: 0,
+        crashes: 0, // Need to pipe this in if possible, or accept 0 for now
+        sb: 0.0,
+        elo: 0.0,
+        elo_diff: None,
+    })).collect()
+}
+
+/// Adds one game's points and W/D/L to both engines' rows and to the points
+/// each won against the other.
+fn record_game(entries_map: &mut HashMap<String, StandingsEntry>, sb_map: &mut HashMap<String, HashMap<String, f64>>, white: &str, black: &str, result: &str) {
+    // Check if engines exist in map (might be disabled/removed ones, but typically they are in config)
+    if !entries_map.contains_key(white) || !entries_map.contains_key(black) { return; }
+
+    // "*" / unfinished games don't count as played
+    let Some((w_pts, b_pts)) = result_points(result) else { return };
+
+    for (engine, points) in [(white, w_pts), (black, b_pts)] {
+        if let Some(entry) = entries_map.get_mut(engine) {
+            entry.games_played += 1;
+            entry.points += points;
+            if points == 1.0 { entry.wins += 1; }
+            else if points == 0.5 { entry.draws += 1; }
+            else { entry.losses += 1; }
         }
     }
 
+    // Track H2H points for SB
+    *sb_map.entry(white.to_string()).or_default().entry(black.to_string()).or_insert(0.0) += w_pts;
+    *sb_map.entry(black.to_string()).or_default().entry(white.to_string()).or_insert(0.0) += b_pts;
+}
+
+/// Works out SB, which needs every engine's final points, then sorts, ranks and
+/// fills in the derived columns.
+fn rank_standings(mut entries_map: HashMap<String, StandingsEntry>, sb_map: &HashMap<String, HashMap<String, f64>>, tiebreak_order: Option<&[String]>) -> Vec<StandingsEntry> {
     // Calculate SB
     // SB = Sum of (Opponent's Final Score) * (Points Won Against Opponent)
-    // Note: This requires Opponent's Final Score, which we have in `entries_map` after first pass.
     let scores: HashMap<String, f64> = entries_map.iter().map(|(k, v)| (k.clone(), v.points)).collect();
 
-    for (player, opponents) in &sb_map {
+    for (player, opponents) in sb_map {
         let mut sb = 0.0;
         for (opponent, points_against) in opponents {
              if let Some(opp_score) = scores.get(opponent) {
@@ -235,29 +469,268 @@ pub fn calculate_standings(schedule: &[crate::types::ScheduledGame], engines: &[
     // Finalize stats (percent, rank, elo)
     let mut entries: Vec<StandingsEntry> = entries_map.into_values().collect();
 
-    // Sort by Points desc, then SB desc, then Wins desc
-    entries.sort_by(|a, b| {
-        b.points.partial_cmp(&a.points).unwrap_or(std::cmp::Ordering::Equal)
-            .then_with(|| b.sb.partial_cmp(&a.sb).unwrap_or(std::cmp::Ordering::Equal))
-            .then_with(|| b.wins.cmp(&a.wins))
-    });
+    // Sort by Points desc, then the tie-breaks in order (SB, then Wins by default)
+    let tiebreaks: Vec<Tiebreak> = match tiebreak_order {
+        Some(names) => names.iter().filter_map(|name| Tiebreak::parse(name)).collect(),
+        None => DEFAULT_TIEBREAKS.to_vec(),
+    };
+    entries.sort_by(|a, b| b.points.partial_cmp(&a.points).unwrap_or(std::cmp::Ordering::Equal));
+    for level in entries.chunk_by_mut(|a, b| a.points == b.points) {
+        break_ties(level, &tiebreaks, sb_map);
+    }
 
     for (i, entry) in entries.iter_mut().enumerate() {
         entry.rank = (i + 1) as u32;
         if entry.games_played > 0 {
             entry.score_percent = (entry.points / entry.games_played as f64) * 100.0;
-
-            // Basic Elo Estimation
-            // P = 1 / (1 + 10^(-D/400))
-            // D = -400 * log10(1/P - 1)
-            let p = entry.points / entry.games_played as f64;
-             if p <= 0.001 { entry.elo = -1000.0; } // Cap
-             else if p >= 0.999 { entry.elo = 1000.0; } // Cap
-             else {
-                 entry.elo = -400.0 * (1.0 / p - 1.0).log10();
-             }
+            // Performance against the field, so ratings are relative to its average
+            entry.elo = score_to_elo(entry.points / entry.games_played as f64);
+            entry.elo_diff = elo_margin(entry.wins, entry.draws, entry.losses);
         }
     }
 
     entries
 }
+
+/// Per-engine ratings stop here; a perfect or zero score would be infinitely far off.
+const ELO_CAP: f64 = 1000.0;
+
+/// D = -400 * log10(1/P - 1), the inverse of P = 1 / (1 + 10^(-D/400)).
+fn score_to_elo(p: f64) -> f64 {
+    if p <= 0.001 {
+        -ELO_CAP
+    } else if p >= 0.999 {
+        ELO_CAP
+    } else {
+        -400.0 * (1.0 / p - 1.0).log10()
+    }
+}
+
+/// Half the width of the 95% interval of an engine's rating, from the spread of
+/// its own results. A record with no spread (all wins, all losses or all draws)
+/// is treated as having one decisive result among its games, so its margin still
+/// shrinks with games instead of collapsing to 0. At 100% or 0% the interval is
+/// cut off by the cap, so the margin is half the distance from its lower (or
+/// upper) end to `ELO_CAP` and never exceeds `ELO_CAP`.
+fn elo_margin(wins: u32, draws: u32, losses: u32) -> Option<f64> {
+    let games = (wins + draws + losses) as f64;
+    if games == 0.0 {
+        return None;
+    }
+    let p = (wins as f64 + draws as f64 * 0.5) / games;
+    let variance = (wins as f64 * (1.0 - p).powi(2) + draws as f64 * (0.5 - p).powi(2) + losses as f64 * p.powi(2)) / games;
+    let variance = variance.max(0.25 / games);
+    let deviation = 1.96 * (variance / games).sqrt();
+    Some((score_to_elo(p + deviation) - score_to_elo(p - deviation)) / 2.0)
+}
+
+/// Running standings and crosstable for a live tournament. Each finished game
+/// only touches its two engines' rows; SB and ranks depend on everyone's points,
+/// so they are worked out when `standings` is asked for.
+#[derive(Clone, Debug, Default)]
+pub struct StandingsTracker {
+    entries: HashMap<String, StandingsEntry>,
+    points_won: HashMap<String, HashMap<String, f64>>, // Player -> Opponent -> Points Won Against
+    head_to_head: HashMap<(String, String), HeadToHead>,
+}
+
+impl StandingsTracker {
+    pub fn new(engines: &[crate::types::EngineConfig]) -> Self {
+        Self { entries: new_entries(engines), ..Self::default() }
+    }
+
+    /// Replays the decided games of a saved schedule, for resumed runs.
+    pub fn from_schedule(schedule: &[ScheduledGame], engines: &[crate::types::EngineConfig]) -> Self {
+        let mut tracker = Self::new(engines);
+        for game in schedule {
+            if let Some(result) = &game.result {
+                tracker.record(&game.white_name, &game.black_name, result);
+            }
+        }
+        tracker
+    }
+
+    /// Counts one game; unfinished results are ignored like in `calculate_standings`.
+    pub fn record(&mut self, white: &str, black: &str, result: &str) {
+        record_game(&mut self.entries, &mut self.points_won, white, black, result);
+        if let Some((w_pts, b_pts)) = result_points(result) {
+            record_head_to_head(&mut self.head_to_head, white, black, w_pts, b_pts);
+        }
+    }
+
+    pub fn standings(&self, tiebreak_order: Option<&[String]>) -> Vec<StandingsEntry> {
+        rank_standings(self.entries.clone(), &self.points_won, tiebreak_order)
+    }
+
+    pub fn head_to_head(&self) -> Vec<HeadToHead> {
+        sorted_head_to_head(self.head_to_head.values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EngineConfig;
+
+    fn engine(name: &str) -> EngineConfig {
+        EngineConfig {
+            id: None,
+            name: name.to_string(),
+            path: String::new(),
+            options: vec![],
+            country_code: None,
+            args: None,
+            working_directory: None,
+            protocol: None,
+            logo_path: None,
+            init_timeout_ms: None,
+            time_control: None,
+            output_buffer_lines: None,
+            limit_elo: None,
+            go_style: None,
+        }
+    }
+
+    fn game(id: usize, white: &str, black: &str, result: &str) -> ScheduledGame {
+        ScheduledGame {
+            id,
+            white_name: white.to_string(),
+            black_name: black.to_string(),
+            state: "Finished".to_string(),
+            result: Some(result.to_string()),
+            start_fen: None,
+            opening_index: None,
+            opening: None,
+        }
+    }
+
+    #[test]
+    fn unfinished_results_are_not_counted() {
+        let engines = vec![engine("A"), engine("B")];
+        let schedule = vec![
+            game(1, "A", "B", "1-0"),
+            game(2, "B", "A", "*"),
+            game(3, "B", "A", "1/2-1/2"),
+            game(4, "A", "B", "*"),
+        ];
+        let standings = calculate_standings(&schedule, &engines, None);
+        let a = standings.iter().find(|e| e.engine_name == "A").unwrap();
+        let b = standings.iter().find(|e| e.engine_name == "B").unwrap();
+        assert_eq!((a.games_played, a.wins, a.draws, a.losses), (2, 1, 1, 0));
+        assert_eq!((b.games_played, b.wins, b.draws, b.losses), (2, 0, 1, 1));
+        assert_eq!(a.points, 1.5);
+        assert_eq!(a.score_percent, 75.0);
+    }
+
+    #[test]
+    fn tiebreaks_apply_in_the_configured_order() {
+        let engines = vec![engine("A"), engine("B"), engine("C"), engine("D")];
+        // A and B finish on 2 points: B beat A and has the better SB, A won more games
+        let schedule = vec![
+            game(1, "A", "C", "1-0"),
+            game(2, "A", "D", "1-0"),
+            game(3, "B", "A", "1-0"),
+            game(4, "B", "C", "1/2-1/2"),
+            game(5, "B", "D", "1/2-1/2"),
+            game(6, "C", "D", "1-0"),
+        ];
+        let order = |tiebreaks: Option<&[String]>| -> Vec<String> {
+            calculate_standings(&schedule, &engines, tiebreaks).into_iter().take(2).map(|e| e.engine_name).collect()
+        };
+        let names = |names: &[&str]| -> Vec<String> { names.iter().map(|n| n.to_string()).collect() };
+
+        assert_eq!(order(None), names(&["B", "A"]));
+        assert_eq!(order(Some(&names(&["wins"]))), names(&["A", "B"]));
+        assert_eq!(order(Some(&names(&["direct_encounter", "wins"]))), names(&["B", "A"]));
+        assert_eq!(order(Some(&names(&["wins", "direct_encounter"]))), names(&["A", "B"]));
+        // Unknown names are skipped rather than reordering anything
+        assert_eq!(order(Some(&names(&["koya", "wins"]))), names(&["A", "B"]));
+    }
+
+    #[test]
+    fn elo_margin_shrinks_with_games_and_stays_capped_at_perfect_scores() {
+        // The same 60% score over more games
+        let margins: Vec<f64> = [10, 100, 1000].iter().map(|&n| elo_margin(n / 2, n / 5, n * 3 / 10).unwrap()).collect();
+        assert!(margins[0] > margins[1] && margins[1] > margins[2], "{:?}", margins);
+        assert!((15.0..25.0).contains(&margins[2]), "{:?}", margins);
+        assert_eq!(elo_margin(0, 0, 0), None);
+
+        let engines = vec![engine("A"), engine("B")];
+        let schedule: Vec<ScheduledGame> = (1..=10).map(|id| game(id, "A", "B", "1-0")).collect();
+        let standings = calculate_standings(&schedule, &engines, None);
+        let (a, b) = (&standings[0], &standings[1]);
+        assert_eq!((a.elo, b.elo), (ELO_CAP, -ELO_CAP));
+        let perfect = a.elo_diff.unwrap();
+        assert_eq!(b.elo_diff, Some(perfect));
+        assert!(perfect > 0.0 && perfect < ELO_CAP);
+        assert!(elo_margin(100, 0, 0).unwrap() < perfect);
+
+        // All draws still get a margin
+        assert!(elo_margin(0, 10, 0).unwrap() > 0.0);
+    }
+
+    #[test]
+    fn tracker_matches_a_full_recompute_after_every_game() {
+        let engines = vec![engine("A"), engine("B"), engine("C"), engine("D")];
+        let schedule = vec![
+            game(1, "A", "B", "1-0"),
+            game(2, "C", "D", "1/2-1/2"),
+            game(3, "B", "C", "*"),
+            game(4, "D", "A", "0-1 (forfeit)"),
+            game(5, "B", "D", "1-0"),
+            game(6, "C", "A", "1/2-1/2"),
+            game(7, "D", "C", "0-1"),
+            game(8, "A", "X", "1-0"), // not a configured engine
+        ];
+        // Name, rank, games, points, SB, wins, draws, losses
+        type Row = (String, u32, u32, f64, f64, u32, u32, u32);
+        let summary = |entries: Vec<StandingsEntry>| -> Vec<Row> {
+            entries.into_iter().map(|e| (e.engine_name, e.rank, e.games_played, e.points, e.sb, e.wins, e.draws, e.losses)).collect()
+        };
+        let crosstable = |rows: Vec<HeadToHead>| -> Vec<(String, String, f64)> {
+            rows.into_iter().map(|r| (r.engine, r.opponent, r.points)).collect()
+        };
+
+        let mut tracker = StandingsTracker::new(&engines);
+        for played in 1..=schedule.len() {
+            let latest = &schedule[played - 1];
+            tracker.record(&latest.white_name, &latest.black_name, latest.result.as_deref().unwrap());
+            let so_far = &schedule[..played];
+            if played < 6 { continue; } // full ties earlier on leave the order up to the hash map
+            assert_eq!(summary(tracker.standings(None)), summary(calculate_standings(so_far, &engines, None)), "after game {}", played);
+            assert_eq!(crosstable(tracker.head_to_head()), crosstable(head_to_head(so_far)), "after game {}", played);
+        }
+        let resumed = StandingsTracker::from_schedule(&schedule, &engines);
+        assert_eq!(summary(resumed.standings(None)), summary(tracker.standings(None)));
+    }
+
+    #[test]
+    fn head_to_head_rows_cover_both_sides() {
+        let schedule = vec![
+            game(1, "A", "B", "1-0"),
+            game(2, "B", "A", "1/2-1/2"),
+            game(3, "A", "C", "0-1 (forfeit)"),
+            game(4, "C", "A", "*"),
+        ];
+        let rows: Vec<(String, String, u32, u32, u32, f64)> = head_to_head(&schedule).into_iter()
+            .map(|r| (r.engine, r.opponent, r.wins, r.draws, r.losses, r.points))
+            .collect();
+        assert_eq!(rows, vec![
+            ("A".to_string(), "B".to_string(), 1, 1, 0, 1.5),
+            ("A".to_string(), "C".to_string(), 0, 0, 1, 0.0),
+            ("B".to_string(), "A".to_string(), 0, 1, 1, 0.5),
+            ("C".to_string(), "A".to_string(), 1, 0, 0, 1.0),
+        ]);
+    }
+
+    #[test]
+    fn tournament_stats_carry_the_crosstable() {
+        let schedule = vec![game(1, "A", "B", "1/2-1/2")];
+        let mut stats = TournamentStats::new(false, None);
+        stats.update_head_to_head(head_to_head(&schedule));
+        let json = serde_json::to_value(&stats).unwrap();
+        let rows = json["head_to_head"].as_array().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| row["points"] == 0.5 && row["draws"] == 1));
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use crate::sprt::SprtConfig;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -12,6 +13,16 @@ pub struct EngineConfig {
     pub working_directory: Option<String>,
     pub protocol: Option<String>, // "uci" or "xboard", default "uci"
     pub logo_path: Option<String>, // Path to engine logo image
+    #[serde(default)]
+    pub init_timeout_ms: Option<u64>, // uciok/readyok wait, default 10s
+    #[serde(default)]
+    pub time_control: Option<TimeControl>, // time odds: own base/inc; overhead, nodestime and movetime stay tournament-wide
+    #[serde(default)]
+    pub output_buffer_lines: Option<usize>, // stdout lines buffered per listener, default 10_000
+    #[serde(default)]
+    pub limit_elo: Option<u32>, // sends UCI_LimitStrength true and this UCI_Elo, overriding both in options
+    #[serde(default)]
+    pub go_style: Option<String>, // "clock" (default), "movetime" or "infinite_stop" for engines with incomplete time handling
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -28,50 +39,241 @@ pub struct AdjudicationConfig {
     pub draw_score: Option<i32>,        // cp
     pub draw_move_number: Option<u32>,  // start checking after this move
     pub draw_move_count: Option<u32>,   // consecutive moves within score
+    #[serde(default)]
+    pub mate_move_count: Option<u32>,   // consecutive moves with a mate score
     pub result_adjudication: bool,      // Syzygy/TB adjudication (implied)
+    #[serde(default)]
+    pub disable_score_adjudication: bool, // skip resign/draw checks based on engine scores
+    #[serde(default = "default_claim_fifty_move")]
+    pub claim_fifty_move: bool,         // claim the 50-move draw; otherwise play on until the 75-move rule
+    #[serde(default)]
+    pub resign_immediate_on_agreement: bool, // resign at once when both engines' latest scores pass the threshold for the same side
+}
+
+fn default_claim_fifty_move() -> bool {
+    true
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OpeningConfig {
     pub file: Option<String>,           // PGN/EPD/FEN file path
     pub fen: Option<String>,            // Direct FEN string
-    pub depth: Option<u32>,             // Moves to play from book
+    pub depth: Option<u32>,             // Plies of a PGN opening line played before engines take over; whole line when unset
     pub order: Option<String>,          // "random", "sequential"
     pub book_path: Option<String>,      // Polyglot bin book path
+    #[serde(default)]
+    pub pins_file: Option<String>,      // JSON game_id -> start FEN, overrides selection
+    #[serde(default)]
+    pub seed: Option<u64>,              // book shuffle and Chess960 positions; picked at start when unset
+    #[serde(default)]
+    pub chess960_sp: Option<u32>,       // Scharnagl number 0-959 used by every Chess960 game
+    #[serde(default)]
+    pub chess960_scope: Option<String>, // without an SP: "game" (default, a fresh draw each game), "pairing" or "tournament"
+    #[serde(default)]
+    pub searchmoves: Option<Vec<String>>, // UCI moves; restricts the first search out of every opening
+    #[serde(default)]
+    pub start_fens: Option<Vec<String>>, // game N of each pairing starts from FEN N (a swapped pair shares one); instead of `file`
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TournamentConfig {
     pub mode: TournamentMode,
     pub engines: Vec<EngineConfig>,
+    #[serde(default)]
+    pub gauntlet_seeds: Option<Vec<usize>>, // engine indices that play everyone else; defaults to [0]
     pub time_control: TimeControl,
-    pub games_count: u32,
+    pub games_count: u32, // per pairing; 0 keeps scheduling until the SPRT resolves
+    #[serde(default)]
+    pub cycles: Option<u32>, // repeat the whole pairing set; colors flip every other cycle
+    #[serde(default)]
+    pub repetitions: Option<u32>, // run the whole event N times; a resumed run carries on from the repetition it stopped in
     pub swap_sides: bool,
     pub opening: OpeningConfig,
     pub variant: String,
-    pub concurrency: Option<u32>,
+    pub concurrency: Option<u32>, // None fits games to the logical cores by engine Threads
+    #[serde(default)]
+    pub max_concurrent_spawns: Option<u32>, // engines launching/handshaking at once
+    #[serde(default)]
+    pub spawn_stagger_ms: Option<u64>, // delay between starting games until `concurrency` are running the first time
+    #[serde(default)]
+    pub isolate_engine_dirs: bool, // own temp working directory per engine when both sides would share files
+    #[serde(default)]
+    pub max_in_game_restarts: Option<u32>, // per engine per game; a crash past this forfeits
+    #[serde(default)]
+    pub abort_on_engine_disable: bool, // stop the tournament instead of forfeiting a disabled engine's games
+    #[serde(default)]
+    pub spawn_failure_limit: Option<u32>, // consecutive spawn/handshake failures before disabling, 3 when unset; 0 acts as 1
+    #[serde(default)]
+    pub max_duration_secs: Option<u64>, // stop starting games after this much wall-clock time; paused time doesn't count, and a resumed run starts the limit over
     pub pgn_path: Option<String>,
     #[serde(default)]
+    pub results_path: Option<String>, // JSON summary written when the tournament ends or is stopped
+    #[serde(default)]
     pub overwrite_pgn: bool,
+    #[serde(default)]
+    pub pgn_sync: bool, // fsync the PGN after every game so a hard crash can't lose finished games
+    #[serde(default)]
+    pub live_pgn_path: Option<String>, // base path; one live file per active game id, removed when it ends
+    #[serde(default)]
+    pub pgn_per_game_dir: Option<String>, // each finished game also goes to game_<id>.pgn here; with no pgn_path, only here
+    #[serde(default)]
+    pub engine_log_dir: Option<String>, // `info string` lines appended to <engine name>.log here
     pub event_name: Option<String>,
+    #[serde(default)]
+    pub site_name: Option<String>, // PGN [Site], "CCRL GUI" when unset
+    #[serde(default)]
+    pub country: Option<String>, // IOC code appended to [Site]
+    #[serde(default)]
+    pub extra_pgn_tags: Option<Vec<(String, String)>>, // emitted after the seven standard tags, in order; tags the GUI writes itself are rejected
+    #[serde(default)]
+    pub round_offset: Option<usize>, // added to PGN [Round]; unset continues after the last round already in the PGN
     pub disabled_engine_ids: Vec<String>,
     pub resume_state_path: Option<String>,
     #[serde(default)]
     pub resume_from_state: bool,
     pub adjudication: AdjudicationConfig,
     #[serde(default)]
+    pub variant_adjudication: HashMap<String, AdjudicationConfig>, // variant name -> override profile
+    #[serde(default)]
     pub sprt_enabled: bool,
     pub sprt_config: Option<SprtConfig>,
+    #[serde(default)]
+    pub pairing_sprt_enabled: bool, // run SPRT per pairing and drop a pairing's games once it concludes
+    #[serde(default)]
+    pub diagnostics: bool, // write game_<id>.diag.json with arbiter decisions next to the PGN
+    #[serde(default)]
+    pub pv_plies: Option<u32>, // PV moves sent with each move for board arrows; default 4, 0 sends none
+    #[serde(default)]
+    pub send_uci_opponent: bool, // tell engines that declare UCI_Opponent who they are playing
+    #[serde(default)]
+    pub engine_health: EngineHealthConfig,
+    #[serde(default)]
+    pub tiebreak_order: Option<Vec<String>>, // "sb", "direct_encounter", "wins" in priority order; default sb then wins
+}
+
+/// Thresholds for the non-fatal engine health warnings.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EngineHealthConfig {
+    pub moves: u32,          // consecutive searches a symptom must last before it warns; 0 disables
+    pub min_nps: u64,        // a search reporting a lower nps looks hung
+    pub hashfull_limit: u32, // permille; at or above it the hash counts as full
+}
+
+impl Default for EngineHealthConfig {
+    fn default() -> Self {
+        Self { moves: 10, min_nps: 1000, hashfull_limit: 1000 }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimeControl {
+    pub base_ms: u64,
+    pub inc_ms: u64,
+    // Nodes per millisecond. When set, clocks are charged by nodes searched instead of
+    // wall-clock time (Stockfish-style `nodestime`); engines without the option fail at init.
+    #[serde(default)]
+    pub nodestime: Option<u64>,
+    // Pipe and GUI latency forgiven on every move (cutechess `timemargin`): subtracted from the
+    // charged time and from the clock sent in `go`. The forfeit guard still allows the full
+    // clock plus its 5s buffer, so this only changes the clocks. Ignored with `nodestime`.
+    #[serde(default = "default_move_overhead_ms")]
+    pub move_overhead_ms: u64,
+    // Fixed think time per move (`go movetime`), at least 1 ms. No clocks run, so nobody
+    // flags; a move still forfeits once it takes 5x this long plus 5s. Takes precedence over base/inc.
+    #[serde(default)]
+    pub movetime_ms: Option<u64>,
+    #[serde(default)]
+    pub control_type: TimeControlType, // how inc_ms is credited; Fischer when unset
+}
+
+/// What `inc_ms` means. The delay types only ever give back time the move used.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum TimeControlType {
+    /// `inc_ms` added after every move
+    #[default]
+    Fischer,
+    /// The clock runs from the start; up to `inc_ms` of what the move used is given back
+    Bronstein,
+    /// The clock only starts once `inc_ms` of the move has passed
+    SimpleDelay,
+}
+
+/// Why `run_tournament` returned.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub enum CompletionReason {
+    AllGamesDone,
+    SprtResolved,
+    Stopped,
+    /// `max_duration_secs` ran out; the games already started were played out
+    TimeLimitReached,
+    Error,
 }
 
+/// Sent once when a tournament ends, however it ended.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct TimeControl { pub base_ms: u64, pub inc_ms: u64 }
+pub struct TournamentComplete {
+    pub stats: crate::stats::TournamentStats,
+    pub games_played: u32, // finished games, byes and forfeits excluded
+    pub elapsed_ms: u64,
+    pub reason: CompletionReason,
+}
+
+fn default_move_overhead_ms() -> u64 {
+    30
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GameUpdate {
     pub fen: String, pub last_move: Option<String>, pub white_time: u64, pub black_time: u64,
     pub move_number: u32, pub result: Option<String>, pub white_engine_idx: usize, pub black_engine_idx: usize,
     pub game_id: usize,
+    #[serde(default)]
+    pub termination: Option<String>, // PGN [Termination] value once the game is over
+    #[serde(default)]
+    pub end_reason: Option<String>, // what ended it: checkmate, stalemate, threefold repetition, an adjudication...
+    #[serde(default)]
+    pub pv_moves: Option<Vec<String>>, // mover's PV from its last info line, starting with the move played
+    #[serde(default)]
+    pub start_fen: Option<String>, // only on a game's first update
+}
+
+/// Minimal per-game result notification for clients that only follow results.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GameCompleted {
+    pub id: usize,
+    pub white: String,
+    pub black: String,
+    pub result: String,
+    pub plies: u32,
+}
+
+/// Free-text `info string` line from an engine during a game.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EngineLogLine {
+    pub engine_id: Option<String>,
+    pub engine_name: String,
+    pub game_id: usize,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PairingSprtConcluded {
+    pub engine_a: String,
+    pub engine_b: String,
+    pub state: String,
+    pub llr: f64,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+/// An advisory about the tournament as a whole rather than one engine, such as an
+/// odd `games_count` or an opening that had to be replaced. Nothing was disabled.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TournamentWarning {
+    pub game_id: Option<usize>,
+    pub message: String,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -98,6 +300,62 @@ pub struct EngineStats {
     pub game_id: usize,
     pub tb_hits: Option<u64>, // Added
     pub hash_full: Option<u32>, // Added
+    #[serde(default)]
+    pub curr_move: Option<String>, // root move being searched; alone on progress-only info lines
+    #[serde(default)]
+    pub curr_move_number: Option<u32>,
+}
+
+/// In-game engine failures; crashes also count engines that failed to start.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EngineFailureCounts {
+    pub crashes: u32,
+    pub timeouts: u32,
+}
+
+/// Score of `engine` against `opponent` over all their finished games.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HeadToHead {
+    pub engine: String,
+    pub opponent: String,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub points: f64,
+}
+
+/// Search totals for one engine, built from the final `info` of each of its moves.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct EngineSearchStats {
+    pub moves: u32,
+    pub total_nodes: u64,
+    pub peak_nps: u64,
+    pub avg_depth: f64,
+    #[serde(default)]
+    pub overshot_moves: u32, // moves far past a soft share of the clock
+    #[serde(skip)]
+    depth_sum: u64,
+}
+
+impl EngineSearchStats {
+    pub fn record_move(&mut self, depth: u32, nodes: u64, nps: u64) {
+        self.moves += 1;
+        self.total_nodes += nodes;
+        self.peak_nps = self.peak_nps.max(nps);
+        self.depth_sum += depth as u64;
+        self.avg_depth = self.depth_sum as f64 / self.moves as f64;
+    }
+
+    pub fn merge(&mut self, other: &EngineSearchStats) {
+        self.moves += other.moves;
+        self.total_nodes += other.total_nodes;
+        self.peak_nps = self.peak_nps.max(other.peak_nps);
+        self.overshot_moves += other.overshot_moves;
+        self.depth_sum += other.depth_sum;
+        if self.moves > 0 {
+            self.avg_depth = self.depth_sum as f64 / self.moves as f64;
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -107,12 +365,20 @@ pub struct ScheduledGame {
     pub black_name: String,
     pub state: String,
     pub result: Option<String>,
+    #[serde(default)]
+    pub start_fen: Option<String>,
+    #[serde(default)]
+    pub opening_index: Option<usize>, // book line picked at scheduling; reused on resume
+    #[serde(default)]
+    pub opening: Option<String>, // opening name, or the start FEN when it has none
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TournamentResumeState {
     pub config: TournamentConfig,
     pub schedule: Vec<ScheduledGame>,
+    #[serde(default)]
+    pub paused: bool, // paused when last saved; `resume_match` starts paused again
 }
 
 // UCI Option Types for Frontend
@@ -146,5 +412,5 @@ pub struct StandingsEntry {
     pub crashes: u32,
     pub sb: f64, // Sonneborn-Berger
     pub elo: f64,
-    pub elo_diff: Option<f64>,
+    pub elo_diff: Option<f64>, // ± 95% margin of `elo`
 }
@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::process::Stdio;
 use tokio::process::Command;
 use tokio::io::{BufReader, AsyncBufReadExt, AsyncWriteExt, BufWriter};
@@ -5,14 +6,35 @@ use tokio::sync::mpsc;
 use tokio::sync::broadcast;
 use anyhow::{Result, Context};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::Mutex;
 use crate::types::UciOption;
+use serde::Serialize;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct EngineInfo {
     pub name: String,
     pub author: String,
-    pub options: Vec<String>,
+    pub options: Vec<UciOption>,
+    pub supports_chess960: bool,       // declares UCI_Chess960
+    pub supports_ponder: bool,         // declares Ponder
+    pub supports_multipv: bool,        // declares MultiPV
+    pub supports_limit_strength: bool, // declares UCI_LimitStrength
+}
+
+impl EngineInfo {
+    fn declares(&self, option: &str) -> bool {
+        self.options.iter().any(|opt| opt.name.eq_ignore_ascii_case(option))
+    }
+
+    /// Sets the `supports_*` flags from the declared options.
+    fn with_capabilities(mut self) -> Self {
+        self.supports_chess960 = self.declares("UCI_Chess960");
+        self.supports_ponder = self.declares("Ponder");
+        self.supports_multipv = self.declares("MultiPV");
+        self.supports_limit_strength = self.declares("UCI_LimitStrength");
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -21,17 +43,64 @@ pub struct AsyncEngine {
     kill_tx: mpsc::Sender<()>,
     pub stdout_broadcast: broadcast::Sender<String>,
     pub is_alive: Arc<Mutex<bool>>,
+    lagged_lines: Arc<AtomicU64>,
+}
+
+/// Lines of engine output a subscriber may fall behind before it starts losing them.
+pub const DEFAULT_OUTPUT_BUFFER_LINES: usize = 10_000;
+
+/// How long the handshake listens for a launch banner before it sends `uci`.
+pub const STARTUP_DRAIN_MS: u64 = 50;
+
+/// A line the engine prints in answer to `uci`. Anything else it prints meanwhile
+/// (banners, copyright notices, network loading messages) is `Chatter`.
+pub(crate) enum HandshakeLine {
+    IdName(String),
+    IdAuthor(String),
+    Option(UciOption),
+    UciOk,
+    Chatter,
+}
+
+pub(crate) fn handshake_line(line: &str) -> HandshakeLine {
+    let line = line.trim();
+    if line == "uciok" {
+        return HandshakeLine::UciOk;
+    }
+    if let Some(name) = line.strip_prefix("id name ") {
+        return HandshakeLine::IdName(name.trim().to_string());
+    }
+    if let Some(author) = line.strip_prefix("id author ") {
+        return HandshakeLine::IdAuthor(author.trim().to_string());
+    }
+    match line.starts_with("option name ").then(|| parse_uci_option(line)).flatten() {
+        Some(opt) => HandshakeLine::Option(opt),
+        None => HandshakeLine::Chatter,
+    }
 }
 
 impl AsyncEngine {
     pub async fn spawn(path: &str) -> Result<Self> {
-        // Fix: Increase buffer to prevents 'Lagged' errors dropping crucial 'bestmove' lines
-        const BROADCAST_BUFFER_SIZE: usize = 10_000;
+        Self::spawn_in(path, None).await
+    }
+
+    /// Spawns the engine with `working_directory` as its current directory, where
+    /// engines put relative hash, learning and log files.
+    pub async fn spawn_in(path: &str, working_directory: Option<&Path>) -> Result<Self> {
+        Self::spawn_with_buffer(path, working_directory, DEFAULT_OUTPUT_BUFFER_LINES).await
+    }
 
+    /// Like `spawn_in`, buffering `buffer_lines` of stdout for each subscriber.
+    /// Chatty engines at long time controls may need more to keep `bestmove`
+    /// from being dropped as a `Lagged` line.
+    pub async fn spawn_with_buffer(path: &str, working_directory: Option<&Path>, buffer_lines: usize) -> Result<Self> {
         let mut cmd = Command::new(path);
         cmd.stdin(Stdio::piped())
            .stdout(Stdio::piped())
            .stderr(Stdio::null());
+        if let Some(dir) = working_directory {
+            cmd.current_dir(dir);
+        }
 
         #[cfg(windows)]
         {
@@ -49,7 +118,7 @@ impl AsyncEngine {
 
         let (stdin_tx, mut stdin_rx) = mpsc::channel::<String>(100);
         let (kill_tx, mut kill_rx) = mpsc::channel::<()>(1);
-        let (stdout_tx, _) = broadcast::channel::<String>(BROADCAST_BUFFER_SIZE);
+        let (stdout_tx, _) = broadcast::channel::<String>(buffer_lines.max(1));
 
         let is_alive = Arc::new(Mutex::new(true));
         let is_alive_clone = is_alive.clone();
@@ -96,10 +165,38 @@ impl AsyncEngine {
             stdin_tx,
             kill_tx,
             stdout_broadcast: stdout_tx,
-            is_alive
+            is_alive,
+            lagged_lines: Arc::new(AtomicU64::new(0)),
         })
     }
 
+    /// Subscribes to the engine's output and discards what it prints on launch,
+    /// before it's been sent `uci`, so a banner can't be taken for part of the
+    /// handshake. Returns the subscription for the handshake and the discarded lines.
+    pub async fn drain_startup_output(&self) -> (broadcast::Receiver<String>, Vec<String>) {
+        let mut rx = self.stdout_broadcast.subscribe();
+        let mut banner = Vec::new();
+        let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_millis(STARTUP_DRAIN_MS);
+        while let Ok(received) = tokio::time::timeout_at(deadline, rx.recv()).await {
+            match received {
+                Ok(line) => banner.push(line),
+                Err(broadcast::error::RecvError::Lagged(count)) => self.record_lag(count),
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+        (rx, banner)
+    }
+
+    /// Counts output lines a subscriber missed after a `RecvError::Lagged(lines)`.
+    pub fn record_lag(&self, lines: u64) {
+        self.lagged_lines.fetch_add(lines, Ordering::Relaxed);
+    }
+
+    /// Output lines lost by all subscribers of this process so far.
+    pub fn lagged_lines(&self) -> u64 {
+        self.lagged_lines.load(Ordering::Relaxed)
+    }
+
     pub async fn send(&self, cmd: String) -> Result<()> {
         if self.stdin_tx.send(cmd).await.is_err() {
             return Err(anyhow::anyhow!("Engine process is dead"));
@@ -128,21 +225,28 @@ impl AsyncEngine {
 }
 
 pub async fn query_engine_options(path: &str) -> Result<Vec<UciOption>> {
+    Ok(identify_engine(path).await?.options)
+}
+
+/// Runs the `uci` handshake and collects `id name`, `id author`, the options and
+/// the capabilities they advertise.
+pub async fn identify_engine(path: &str) -> Result<EngineInfo> {
     let engine = AsyncEngine::spawn(path).await?;
-    let mut rx = engine.stdout_broadcast.subscribe();
+    let (mut rx, _banner) = engine.drain_startup_output().await;
 
     engine.send("uci".to_string()).await?;
 
-    let options = tokio::time::timeout(tokio::time::Duration::from_secs(5), async {
-        let mut options = Vec::new();
+    let info = tokio::time::timeout(tokio::time::Duration::from_secs(5), async {
+        let mut info = EngineInfo::default();
         loop {
             match rx.recv().await {
-                Ok(line) => {
-                    if line == "uciok" { return Ok(options); }
-                    if line.starts_with("option name ") {
-                        if let Some(opt) = parse_uci_option(&line) { options.push(opt); }
-                    }
-                }
+                Ok(line) => match handshake_line(&line) {
+                    HandshakeLine::UciOk => return Ok(info.with_capabilities()),
+                    HandshakeLine::IdName(name) => info.name = name,
+                    HandshakeLine::IdAuthor(author) => info.author = author,
+                    HandshakeLine::Option(opt) => info.options.push(opt),
+                    HandshakeLine::Chatter => {}
+                },
                 Err(broadcast::error::RecvError::Lagged(_)) => continue,
                 Err(_) => return Err(anyhow::anyhow!("Engine disconnected")),
             }
@@ -151,8 +255,8 @@ pub async fn query_engine_options(path: &str) -> Result<Vec<UciOption>> {
 
     let _ = engine.quit().await;
 
-    match options {
-        Ok(Ok(options)) => Ok(options),
+    match info {
+        Ok(Ok(info)) => Ok(info),
         Ok(Err(err)) => Err(err),
         Err(_) => {
             let _ = engine.kill().await;
@@ -161,7 +265,7 @@ pub async fn query_engine_options(path: &str) -> Result<Vec<UciOption>> {
     }
 }
 
-fn parse_uci_option(line: &str) -> Option<UciOption> {
+pub(crate) fn parse_uci_option(line: &str) -> Option<UciOption> {
     let parts: Vec<&str> = line.split_whitespace().collect();
     let name_idx = parts.iter().position(|&x| x == "name")?;
     let type_idx = parts.iter().position(|&x| x == "type")?;
@@ -0,0 +1,93 @@
+use crate::arbiter::initialize_engine;
+use crate::epd::search;
+use crate::types::EngineConfig;
+use crate::uci::AsyncEngine;
+use serde::Serialize;
+use shakmaty::fen::Fen;
+use shakmaty::uci::Uci;
+use shakmaty::{Chess, EnPassantMode, Position};
+
+/// Plies the engine plays against itself from the start position.
+const SELF_PLAY_PLIES: u32 = 10;
+const VERIFY_MOVETIME_MS: u64 = 250;
+
+/// Outcome of `verify_engine`. A failed handshake or a bad move is reported here
+/// rather than as an error, so the UI can show what went wrong.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct EngineVerification {
+    pub handshake_ok: bool,
+    pub moves_played: u32,
+    pub moves_legal: bool,
+    pub average_nps: Option<u64>,
+    pub warnings: Vec<String>,
+}
+
+/// Handshakes with the engine at `path` and has it play a few moves against
+/// itself, checking each `bestmove` arrives in time and is legal.
+pub async fn verify_engine(path: &str) -> anyhow::Result<EngineVerification> {
+    let config = engine_at(path);
+    let process = AsyncEngine::spawn(path).await?;
+    let mut report = EngineVerification::default();
+    if let Err(err) = initialize_engine(&process, &config, 0, "standard", None, None).await {
+        report.warnings.push(format!("Handshake failed: {}", err));
+        let _ = process.kill().await;
+        return Ok(report);
+    }
+    report.handshake_ok = true;
+    report.moves_legal = true;
+
+    let mut pos = Chess::default();
+    let mut nps_samples = Vec::new();
+    while report.moves_played < SELF_PLAY_PLIES && !pos.is_game_over() {
+        let ply = report.moves_played + 1;
+        let fen = Fen::from_position(pos.clone(), EnPassantMode::Legal).to_string();
+        let outcome = match search(&process, &fen, VERIFY_MOVETIME_MS).await {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                report.warnings.push(format!("Ply {}: {}", ply, err));
+                break;
+            }
+        };
+        if outcome.nps > 0 {
+            nps_samples.push(outcome.nps);
+        }
+        let legal = outcome.bestmove.as_deref()
+            .and_then(|mv| mv.parse::<Uci>().ok())
+            .and_then(|uci| uci.to_move(&pos).ok());
+        let Some(m) = legal else {
+            report.moves_legal = false;
+            report.warnings.push(format!("Ply {}: {} is not a legal move in {}", ply, outcome.bestmove.as_deref().unwrap_or("null move"), fen));
+            break;
+        };
+        pos.play_unchecked(&m);
+        report.moves_played += 1;
+    }
+    if nps_samples.is_empty() {
+        report.warnings.push("Engine never reported nps".to_string());
+    } else {
+        report.average_nps = Some(nps_samples.iter().sum::<u64>() / nps_samples.len() as u64);
+    }
+    let _ = process.quit().await;
+    Ok(report)
+}
+
+/// An engine config for a standalone check of the engine at `path`, with no
+/// options or per-engine overrides.
+pub(crate) fn engine_at(path: &str) -> EngineConfig {
+    EngineConfig {
+        id: None,
+        name: path.to_string(),
+        path: path.to_string(),
+        options: Vec::new(),
+        country_code: None,
+        args: None,
+        working_directory: None,
+        protocol: None,
+        logo_path: None,
+        init_timeout_ms: None,
+        time_control: None,
+        output_buffer_lines: None,
+        limit_elo: None,
+        go_style: None,
+    }
+}
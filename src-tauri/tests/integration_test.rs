@@ -1,15 +1,46 @@
 use mini_tcec_lib::types::*;
-use mini_tcec_lib::arbiter::Arbiter;
+use mini_tcec_lib::arbiter::{Arbiter, ArbiterChannels, ArbiterReceivers};
 use std::sync::Arc;
-use tokio::sync::mpsc;
 
-#[tokio::test]
-async fn test_match_simulation() {
+/// A mock engine wrapper script, deleted once the test drops it.
+#[cfg(unix)]
+struct MockScript {
+    path: String,
+}
+
+#[cfg(unix)]
+impl Drop for MockScript {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// The mock engine behind a wrapper script that sets its `MOCK_ENGINE_*` variables.
+#[cfg(unix)]
+fn mock_engine_with(name: &str, env: &[(&str, &str)]) -> MockScript {
+    use std::os::unix::fs::PermissionsExt;
+    let path = std::env::temp_dir().join(format!("mock_engine_{}_{}.sh", std::process::id(), name));
+    let vars: Vec<String> = env.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+    std::fs::write(&path, format!("#!/bin/sh\nexec env {} \"{}\"\n", vars.join(" "), env!("CARGO_BIN_EXE_mock-engine"))).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    MockScript { path: path.to_string_lossy().to_string() }
+}
+
+/// Every engine answers e2e4, so Black's first reply is illegal and White wins.
+#[cfg(unix)]
+fn always_e2e4(config: &mut TournamentConfig, name: &str) -> MockScript {
+    let script = mock_engine_with(name, &[("MOCK_ENGINE_MODE", "e2e4")]);
+    for engine in &mut config.engines {
+        engine.path = script.path.clone();
+    }
+    script
+}
+
+fn mock_config(pgn_path: &str) -> TournamentConfig {
     // Path to mock-engine using Cargo's environment variable for integration tests
     let path_str = env!("CARGO_BIN_EXE_mock-engine").to_string();
-    let pgn_path = "test_integration.pgn".to_string();
 
-    let config = TournamentConfig {
+    TournamentConfig {
         mode: TournamentMode::Match,
         engines: vec![
             EngineConfig {
@@ -22,6 +53,11 @@ async fn test_match_simulation() {
                 working_directory: None,
                 protocol: None,
                 logo_path: None,
+                init_timeout_ms: None,
+                time_control: None,
+                output_buffer_lines: None,
+                limit_elo: None,
+                go_style: None,
             },
             EngineConfig {
                 id: None,
@@ -33,10 +69,15 @@ async fn test_match_simulation() {
                 working_directory: None,
                 protocol: None,
                 logo_path: None,
+                init_timeout_ms: None,
+                time_control: None,
+                output_buffer_lines: None,
+                limit_elo: None,
+                go_style: None,
             },
             EngineConfig {
                 id: None,
-                name: "MockWhite".into(),
+                name: "MockWhite 2".into(),
                 path: path_str.clone(),
                 options: vec![],
                 country_code: None,
@@ -44,10 +85,15 @@ async fn test_match_simulation() {
                 working_directory: None,
                 protocol: None,
                 logo_path: None,
+                init_timeout_ms: None,
+                time_control: None,
+                output_buffer_lines: None,
+                limit_elo: None,
+                go_style: None,
             },
             EngineConfig {
                 id: None,
-                name: "MockBlack".into(),
+                name: "MockBlack 2".into(),
                 path: path_str,
                 options: vec![],
                 country_code: None,
@@ -55,10 +101,18 @@ async fn test_match_simulation() {
                 working_directory: None,
                 protocol: None,
                 logo_path: None,
+                init_timeout_ms: None,
+                time_control: None,
+                output_buffer_lines: None,
+                limit_elo: None,
+                go_style: None,
             },
         ],
-        time_control: TimeControl { base_ms: 1000, inc_ms: 100 },
+        gauntlet_seeds: None,
+        time_control: TimeControl { base_ms: 1000, inc_ms: 100, nodestime: None, move_overhead_ms: 30, movetime_ms: None, control_type: TimeControlType::Fischer },
         games_count: 2,
+        cycles: None,
+        repetitions: None,
         swap_sides: true,
         opening: OpeningConfig {
             file: None,
@@ -66,11 +120,34 @@ async fn test_match_simulation() {
             depth: None,
             order: None,
             book_path: None,
+            pins_file: None,
+            seed: None,
+            chess960_sp: None,
+            chess960_scope: None,
+            searchmoves: None,
+            start_fens: None,
         },
         variant: "standard".to_string(),
         concurrency: Some(1),
-        pgn_path: Some(pgn_path.clone()),
+        max_concurrent_spawns: None,
+        isolate_engine_dirs: false,
+        max_in_game_restarts: None,
+        abort_on_engine_disable: false,
+        spawn_failure_limit: None,
+        max_duration_secs: None,
+        spawn_stagger_ms: None,
+        pgn_path: Some(pgn_path.to_string()),
+        results_path: None,
+        overwrite_pgn: false,
+        pgn_sync: false,
+        live_pgn_path: None,
+        pgn_per_game_dir: None,
+        engine_log_dir: None,
         event_name: None,
+        site_name: None,
+        country: None,
+        extra_pgn_tags: None,
+        round_offset: None,
         disabled_engine_ids: Vec::new(),
         resume_state_path: None,
         resume_from_state: false,
@@ -80,25 +157,32 @@ async fn test_match_simulation() {
             draw_score: None,
             draw_move_number: None,
             draw_move_count: None,
+            mate_move_count: None,
             result_adjudication: false,
+            disable_score_adjudication: false,
+            claim_fifty_move: true,
+            resign_immediate_on_agreement: false,
         },
+        variant_adjudication: Default::default(),
         sprt_enabled: false,
         sprt_config: None,
-    };
+        pairing_sprt_enabled: false,
+        diagnostics: false,
+        pv_plies: None,
+        send_uci_opponent: false,
+        engine_health: Default::default(),
+        tiebreak_order: None,
+    }
+}
 
-    let (game_tx, mut game_rx) = mpsc::channel(100);
-    // Keep receivers alive and drain them to prevent channel closure/blocking
-    let (stats_tx, mut stats_rx) = mpsc::channel(100);
-    let (tourney_stats_tx, mut tourney_stats_rx) = mpsc::channel(100);
-    let (schedule_update_tx, mut schedule_update_rx) = mpsc::channel(100);
-    let (error_tx, mut error_rx) = mpsc::channel(100);
+#[tokio::test]
+async fn test_match_simulation() {
+    let pgn_path = "test_integration.pgn".to_string();
+    let config = mock_config(&pgn_path);
 
-    tokio::spawn(async move { while stats_rx.recv().await.is_some() {} });
-    tokio::spawn(async move { while tourney_stats_rx.recv().await.is_some() {} });
-    tokio::spawn(async move { while schedule_update_rx.recv().await.is_some() {} });
-    tokio::spawn(async move { while error_rx.recv().await.is_some() {} });
+    let (channels, ArbiterReceivers { game_update_rx: mut game_rx, .. }) = ArbiterChannels::new(100);
 
-    let arbiter = Arbiter::new(config, game_tx, stats_tx, tourney_stats_tx, schedule_update_tx, error_tx).await.expect("Failed to create arbiter");
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
     let arbiter = Arc::new(arbiter);
 
     // Run match in background
@@ -137,3 +221,959 @@ async fn test_match_simulation() {
         let _ = std::fs::remove_file(pgn_path);
     }
 }
+
+/// Runs `config` until `plies` moves are played, returning the game updates and
+/// the clock updates sent up to then.
+async fn first_plies(config: TournamentConfig, plies: usize) -> (Vec<GameUpdate>, Vec<TimeUpdate>) {
+    let (channels, ArbiterReceivers { mut time_update_rx, game_update_rx: mut game_rx, .. }) = ArbiterChannels::new(100);
+    let arbiter = Arc::new(Arbiter::new(config, channels).await.expect("Failed to create arbiter"));
+    let runner = arbiter.clone();
+    tokio::spawn(async move { let _ = runner.run_tournament().await; });
+
+    let mut updates: Vec<GameUpdate> = Vec::new();
+    while updates.iter().filter(|update| update.last_move.is_some()).count() < plies {
+        let update = game_rx.recv().await.expect("game ended early");
+        assert!(update.result.is_none(), "game ended early: {:?}", update);
+        updates.push(update);
+    }
+    arbiter.stop().await;
+    let time_updates = std::iter::from_fn(|| time_update_rx.try_recv().ok()).collect();
+    (updates, time_updates)
+}
+
+#[tokio::test]
+async fn test_move_updates_carry_the_engine_pv() {
+    let pgn_path = "test_pv_moves.pgn";
+    let (updates, _) = first_plies(mock_config(pgn_path), 2).await;
+    for update in updates.iter().filter(|update| update.last_move.is_some()) {
+        // The mock's PV is just the move it plays
+        assert_eq!(update.pv_moves, update.last_move.clone().map(|m| vec![m]));
+    }
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[tokio::test]
+async fn test_clock_of_the_side_to_move_ticks_down() {
+    let pgn_path = "test_time_updates.pgn";
+    let (_, time_updates) = first_plies(mock_config(pgn_path), 1).await;
+    let lowest_white = time_updates.iter().map(|update| update.white_time).min();
+    assert!(lowest_white.is_some_and(|ms| ms < 1000), "white's clock never ticked: {:?}", lowest_white);
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[tokio::test]
+async fn test_only_the_first_update_carries_the_start_position() {
+    let pgn_path = "test_start_fen.pgn";
+    let (updates, _) = first_plies(mock_config(pgn_path), 2).await;
+    let with_start: Vec<usize> = updates.iter().enumerate()
+        .filter(|(_, update)| update.start_fen.is_some())
+        .map(|(idx, _)| idx)
+        .collect();
+    assert_eq!(with_start, vec![0]);
+    assert!(updates[0].last_move.is_none());
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_movetime_leaves_clocks_untouched() {
+    let pgn_path = "test_movetime.pgn".to_string();
+    let mut config = mock_config(&pgn_path);
+    config.engines.truncate(2);
+    config.games_count = 1;
+    // The mock always thinks for a second, well inside 5x this
+    config.time_control.movetime_ms = Some(300);
+    let _script = always_e2e4(&mut config, "movetime");
+
+    let (channels, ArbiterReceivers { game_update_rx: mut game_rx, .. }) = ArbiterChannels::new(100);
+
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    tokio::spawn(async move { let _ = arbiter.run_tournament().await; });
+
+    let mut moves = 0;
+    while let Some(update) = game_rx.recv().await {
+        if update.last_move.is_some() {
+            moves += 1;
+            assert_eq!((update.white_time, update.black_time), (1000, 1000));
+        }
+        if update.result.is_some() {
+            break;
+        }
+    }
+    assert!(moves > 0);
+
+    if std::path::Path::new(&pgn_path).exists() {
+        let _ = std::fs::remove_file(pgn_path);
+    }
+}
+
+#[tokio::test]
+async fn test_pause_stops_the_search_without_charging_the_clock() {
+    let pgn_path = "test_pause.pgn".to_string();
+    let mut config = mock_config(&pgn_path);
+    config.engines.truncate(2);
+    config.games_count = 1;
+    config.time_control.base_ms = 10_000;
+
+    let (channels, ArbiterReceivers { mut stats_rx, game_update_rx: mut game_rx, .. }) = ArbiterChannels::new(100);
+
+    let arbiter = Arc::new(Arbiter::new(config, channels).await.expect("Failed to create arbiter"));
+    let runner = arbiter.clone();
+    tokio::spawn(async move { let _ = runner.run_tournament().await; });
+
+    // White's first info line means it is searching
+    stats_rx.recv().await.expect("no engine output");
+    arbiter.set_paused(true).await;
+    tokio::spawn(async move { while stats_rx.recv().await.is_some() {} });
+
+    // The mock would answer a second after its go; paused, nothing may be played
+    let paused_for = std::time::Duration::from_millis(2500);
+    let during_pause = tokio::time::timeout(paused_for, async {
+        while let Some(update) = game_rx.recv().await {
+            if update.last_move.is_some() {
+                return update;
+            }
+        }
+        panic!("game channel closed");
+    }).await;
+    assert!(during_pause.is_err(), "a move was played while paused");
+
+    arbiter.set_paused(false).await;
+    let first_move = loop {
+        let update = game_rx.recv().await.expect("game channel closed");
+        if update.last_move.is_some() {
+            break update;
+        }
+    };
+    // About a second of thinking plus the increment; the pause itself is free
+    assert!(first_move.white_time > 8_500, "white_time {}", first_move.white_time);
+
+    arbiter.stop().await;
+    if std::path::Path::new(&pgn_path).exists() {
+        let _ = std::fs::remove_file(pgn_path);
+    }
+}
+
+#[tokio::test]
+async fn test_engine_time_control_gives_time_odds() {
+    let pgn_path = "test_time_odds.pgn".to_string();
+    let mut config = mock_config(&pgn_path);
+    config.engines.truncate(2);
+    config.games_count = 1;
+    config.swap_sides = false;
+    config.engines[1].time_control = Some(TimeControl { base_ms: 5000, inc_ms: 0, nodestime: None, move_overhead_ms: 30, movetime_ms: None, control_type: TimeControlType::Fischer });
+
+    let (channels, ArbiterReceivers { game_update_rx: mut game_rx, .. }) = ArbiterChannels::new(100);
+
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    tokio::spawn(async move { let _ = arbiter.run_tournament().await; });
+
+    // White's one-second think runs down its own 1s clock; Black's 5s is untouched
+    let first_move = loop {
+        let update = game_rx.recv().await.expect("game ended without a move");
+        if update.last_move.is_some() {
+            break update;
+        }
+    };
+    assert_eq!(first_move.black_time, 5000);
+    assert!(first_move.white_time < 1000);
+
+    if std::path::Path::new(&pgn_path).exists() {
+        let _ = std::fs::remove_file(pgn_path);
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_illegal_move_sets_termination() {
+    let pgn_path = "test_illegal_termination.pgn".to_string();
+    let mut config = mock_config(&pgn_path);
+    // Black's reply is e2e4 as well, which is illegal
+    config.engines.truncate(2);
+    config.games_count = 1;
+    let _script = always_e2e4(&mut config, "illegal");
+
+    let (channels, ArbiterReceivers { game_update_rx: mut game_rx, .. }) = ArbiterChannels::new(100);
+
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    tokio::spawn(async move { let _ = arbiter.run_tournament().await; });
+
+    let mut final_update = None;
+    while let Some(update) = game_rx.recv().await {
+        if update.result.is_some() {
+            final_update = Some(update);
+            break;
+        }
+        assert_eq!(update.termination, None);
+    }
+    let final_update = final_update.expect("game never finished");
+    assert_eq!(final_update.result.as_deref(), Some("1-0"));
+    assert_eq!(final_update.termination.as_deref(), Some("illegal move"));
+
+    if std::path::Path::new(&pgn_path).exists() {
+        let _ = std::fs::remove_file(pgn_path);
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_per_game_pgn_files_are_written_with_the_master_pgn() {
+    let pgn_path = "test_per_game_master.pgn".to_string();
+    let dir = std::env::temp_dir().join(format!("per_game_pgn_{}", std::process::id()));
+    let mut config = mock_config(&pgn_path);
+    config.engines.truncate(2);
+    config.games_count = 1;
+    config.overwrite_pgn = true;
+    config.pgn_per_game_dir = Some(dir.to_string_lossy().to_string());
+    let _script = always_e2e4(&mut config, "per_game");
+
+    let (channels, ArbiterReceivers { mut game_completed_rx, .. }) = ArbiterChannels::new(100);
+
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    tokio::spawn(async move { let _ = arbiter.run_tournament().await; });
+
+    // Completion is reported just before the PGNs are written
+    assert!(game_completed_rx.recv().await.is_some());
+    for _ in 0..50 {
+        if dir.join("game_1.pgn").exists() && std::fs::metadata(&pgn_path).is_ok_and(|m| m.len() > 0) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    let game_pgn = std::fs::read_to_string(dir.join("game_1.pgn")).expect("per-game PGN missing");
+    assert!(game_pgn.contains("[Result \"1-0\"]"));
+    let master = std::fs::read_to_string(&pgn_path).expect("master PGN missing");
+    assert_eq!(master, game_pgn);
+
+    let _ = std::fs::remove_dir_all(&dir);
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_per_game_dir_without_pgn_path_skips_the_master_pgn() {
+    let dir = std::env::temp_dir().join(format!("per_game_only_{}", std::process::id()));
+    let mut config = mock_config("unused.pgn");
+    config.pgn_path = None;
+    config.engines.truncate(2);
+    config.games_count = 1;
+    config.round_offset = Some(10);
+    config.pgn_per_game_dir = Some(dir.to_string_lossy().to_string());
+    let _script = always_e2e4(&mut config, "per_game_only");
+    // Other tests may leave a default master PGN behind; this run must not touch it
+    let master_len = || std::fs::metadata("tournament.pgn").ok().map(|metadata| metadata.len());
+    let master_before = master_len();
+
+    let (channels, _) = ArbiterChannels::new(100);
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    tokio::time::timeout(std::time::Duration::from_secs(30), arbiter.run_tournament()).await
+        .expect("tournament should finish")
+        .expect("tournament failed");
+    for _ in 0..50 {
+        if dir.join("game_1.pgn").exists() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    // Named after its game id, even when its round is offset
+    let game_pgn = std::fs::read_to_string(dir.join("game_1.pgn")).expect("per-game PGN missing");
+    assert!(game_pgn.contains("[Round \"11\"]"));
+    assert!(!dir.join("game_11.pgn").exists());
+    assert_eq!(master_len(), master_before, "no master PGN should be written");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_info_strings_become_engine_log_lines() {
+    let pgn_path = "test_engine_log.pgn";
+    let dir = std::env::temp_dir().join(format!("engine_log_{}", std::process::id()));
+    let mut config = mock_config(pgn_path);
+    config.engines.truncate(2);
+    config.games_count = 1;
+    config.engine_log_dir = Some(dir.to_string_lossy().to_string());
+    let _script = always_e2e4(&mut config, "engine_log");
+
+    let (channels, ArbiterReceivers { mut engine_log_rx, mut game_completed_rx, .. }) = ArbiterChannels::new(100);
+
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    tokio::spawn(async move { let _ = arbiter.run_tournament().await; });
+
+    // White's e2e4 is legal, Black's isn't, so each engine searches once
+    assert!(game_completed_rx.recv().await.is_some());
+    let mut lines = Vec::new();
+    while lines.len() < 2 {
+        lines.push(tokio::time::timeout(std::time::Duration::from_secs(5), engine_log_rx.recv()).await.expect("no engine log line").unwrap());
+    }
+    lines.sort_by(|a, b| a.engine_name.cmp(&b.engine_name));
+    let logged: Vec<(&str, usize, &str)> = lines.iter().map(|line| (line.engine_name.as_str(), line.game_id, line.message.as_str())).collect();
+    assert_eq!(logged, vec![
+        ("MockBlack", 1, "mock engine searching 20 legal moves"),
+        ("MockWhite", 1, "mock engine searching 20 legal moves"),
+    ]);
+
+    let log = std::fs::read_to_string(dir.join("MockWhite.log")).expect("engine log file missing");
+    assert_eq!(log, "[game 1] mock engine searching 20 legal moves\n");
+    let _ = std::fs::remove_dir_all(&dir);
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+/// Plays the first game of `config` to the end and returns its final update and move count.
+#[cfg(unix)]
+async fn play_one_game(config: TournamentConfig) -> (GameUpdate, usize) {
+    let (channels, ArbiterReceivers { game_update_rx: mut game_rx, .. }) = ArbiterChannels::new(100);
+
+    let arbiter = Arc::new(Arbiter::new(config, channels).await.expect("Failed to create arbiter"));
+    let runner = arbiter.clone();
+    tokio::spawn(async move { let _ = runner.run_tournament().await; });
+
+    let mut moves = 0;
+    let final_update = loop {
+        let update = game_rx.recv().await.expect("game never finished");
+        if update.last_move.is_some() {
+            moves += 1;
+        }
+        if update.result.is_some() {
+            break update;
+        }
+    };
+    arbiter.stop().await;
+    (final_update, moves)
+}
+
+/// Two mock engines at `think_ms` a move, White before Black, one game.
+#[cfg(unix)]
+fn mock_pair(pgn_path: &str, name: &str, think_ms: &str, black_env: &[(&str, &str)]) -> (TournamentConfig, [MockScript; 2]) {
+    let mut config = mock_config(pgn_path);
+    config.engines.truncate(2);
+    config.games_count = 1;
+    config.swap_sides = false;
+    config.time_control.base_ms = 60_000;
+    let white = mock_engine_with(&format!("{}_white", name), &[("MOCK_ENGINE_THINK_MS", think_ms)]);
+    let mut env = vec![("MOCK_ENGINE_THINK_MS", think_ms)];
+    env.extend_from_slice(black_env);
+    let black = mock_engine_with(&format!("{}_black", name), &env);
+    config.engines[0].path = white.path.clone();
+    config.engines[1].path = black.path.clone();
+    (config, [white, black])
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_mock_resigning_is_adjudicated() {
+    let pgn_path = "test_mock_resign.pgn";
+    let (mut config, _scripts) = mock_pair(pgn_path, "resign", "100", &[("MOCK_ENGINE_MODE", "resign")]);
+    config.adjudication.resign_score = Some(500);
+    config.adjudication.resign_move_count = Some(1);
+
+    let (final_update, moves) = play_one_game(config).await;
+    assert_eq!(final_update.result.as_deref(), Some("1-0"));
+    assert_eq!(final_update.termination.as_deref(), Some("adjudication"));
+    assert_eq!(moves, 2);
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_mate_agreed_by_both_engines_ends_the_game_at_once() {
+    let pgn_path = "test_mock_mate_agreed.pgn";
+    let (mut config, _scripts) = mock_pair(pgn_path, "mate_agreed", "100", &[("MOCK_ENGINE_MATE", "-3")]);
+    let white = mock_engine_with("mate_agreed_mating", &[("MOCK_ENGINE_THINK_MS", "100"), ("MOCK_ENGINE_MATE", "3")]);
+    config.engines[0].path = white.path.clone();
+    // Far off, so only the agreement can end the game
+    config.adjudication.mate_move_count = Some(20);
+
+    let (final_update, moves) = play_one_game(config).await;
+    assert_eq!(final_update.result.as_deref(), Some("1-0"));
+    assert_eq!(final_update.termination.as_deref(), Some("adjudication"));
+    assert_eq!(final_update.end_reason.as_deref(), Some("mate adjudication"));
+    assert_eq!(moves, 2);
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_mate_reported_for_the_move_count_is_adjudicated() {
+    let pgn_path = "test_mock_mate_count.pgn";
+    let (mut config, _scripts) = mock_pair(pgn_path, "mate_count", "100", &[]);
+    let white = mock_engine_with("mate_count_white", &[("MOCK_ENGINE_THINK_MS", "100"), ("MOCK_ENGINE_MATE", "4")]);
+    config.engines[0].path = white.path.clone();
+    config.adjudication.mate_move_count = Some(1);
+    // A mate score must not pass for a huge centipawn eval
+    config.adjudication.resign_score = Some(500);
+    config.adjudication.resign_move_count = Some(1);
+
+    let (final_update, moves) = play_one_game(config).await;
+    assert_eq!(final_update.result.as_deref(), Some("1-0"));
+    assert_eq!(final_update.end_reason.as_deref(), Some("mate adjudication"));
+    assert_eq!(moves, 1);
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_contradicting_mate_claims_are_not_adjudicated() {
+    let pgn_path = "test_mock_mate_contradicting.pgn";
+    // Each side claims it is the one mating
+    let (mut config, _scripts) = mock_pair(pgn_path, "mate_contradicting", "50", &[("MOCK_ENGINE_MATE", "4")]);
+    let white = mock_engine_with("mate_contradicting_white", &[("MOCK_ENGINE_THINK_MS", "50"), ("MOCK_ENGINE_MATE", "4")]);
+    config.engines[0].path = white.path.clone();
+    config.adjudication.mate_move_count = Some(2);
+
+    let (channels, ArbiterReceivers { game_update_rx: mut game_rx, .. }) = ArbiterChannels::new(100);
+    let arbiter = Arc::new(Arbiter::new(config, channels).await.expect("Failed to create arbiter"));
+    let runner = arbiter.clone();
+    tokio::spawn(async move { let _ = runner.run_tournament().await; });
+
+    let mut moves = 0;
+    while moves < 6 {
+        let update = game_rx.recv().await.expect("game never started");
+        assert!(update.result.is_none(), "adjudicated after {} moves: {:?}", moves, update.end_reason);
+        if update.last_move.is_some() {
+            moves += 1;
+        }
+    }
+    arbiter.stop().await;
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_mock_draw_scores_are_adjudicated() {
+    let pgn_path = "test_mock_draw.pgn";
+    let (mut config, _scripts) = mock_pair(pgn_path, "draw", "100", &[("MOCK_ENGINE_MODE", "draw")]);
+    config.engines[0].path = config.engines[1].path.clone();
+    config.adjudication.draw_score = Some(5);
+    config.adjudication.draw_move_number = Some(1);
+    config.adjudication.draw_move_count = Some(4);
+
+    let (final_update, moves) = play_one_game(config).await;
+    assert_eq!(final_update.result.as_deref(), Some("1/2-1/2"));
+    assert_eq!(final_update.termination.as_deref(), Some("adjudication"));
+    assert_eq!(moves, 4);
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+/// Polls for up to two seconds for `path` to exist (or not), as the live PGN writer runs on its own task.
+#[cfg(unix)]
+async fn wait_for_file(path: &std::path::Path, exists: bool) -> bool {
+    for _ in 0..40 {
+        if path.exists() == exists {
+            return true;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    false
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_live_pgn_file_is_removed_when_its_game_ends() {
+    let pgn_path = "test_live_pgn.pgn";
+    let live_dir = std::env::temp_dir().join(format!("live_pgn_{}", std::process::id()));
+    std::fs::create_dir_all(&live_dir).unwrap();
+    let (mut config, _scripts) = mock_pair(pgn_path, "live_pgn", "300", &[("MOCK_ENGINE_MODE", "resign")]);
+    config.live_pgn_path = Some(live_dir.join("live.pgn").to_string_lossy().to_string());
+    config.adjudication.resign_score = Some(500);
+    config.adjudication.resign_move_count = Some(1);
+    let live_file = live_dir.join("live_1.pgn");
+
+    let (channels, ArbiterReceivers { game_update_rx: mut game_rx, .. }) = ArbiterChannels::new(100);
+    let arbiter = Arc::new(Arbiter::new(config, channels).await.expect("Failed to create arbiter"));
+    let runner = arbiter.clone();
+    tokio::spawn(async move { let _ = runner.run_tournament().await; });
+
+    // White's move is written while Black is still thinking
+    let mut written_mid_game = false;
+    loop {
+        let update = game_rx.recv().await.expect("game never finished");
+        if update.result.is_some() {
+            break;
+        }
+        if update.last_move.is_some() && !written_mid_game {
+            written_mid_game = wait_for_file(&live_file, true).await;
+        }
+    }
+    let removed = wait_for_file(&live_file, false).await;
+    arbiter.stop().await;
+    let _ = std::fs::remove_dir_all(&live_dir);
+    let _ = std::fs::remove_file(pgn_path);
+
+    assert!(written_mid_game, "the live file should follow the game while it runs");
+    assert!(removed, "the live file should go once the game is over");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_stalemate_is_a_draw_by_the_rules() {
+    let pgn_path = "test_stalemate.pgn";
+    let (mut config, _scripts) = mock_pair(pgn_path, "stalemate", "100", &[]);
+    // h5h6 is the mock's first legal move and leaves Black without one
+    config.opening.fen = Some("7k/5K1p/8/7P/8/8/8/8 w - - 0 1".to_string());
+
+    let (final_update, moves) = play_one_game(config).await;
+    assert_eq!(moves, 1);
+    assert_eq!(final_update.result.as_deref(), Some("1/2-1/2"));
+    assert_eq!(final_update.termination.as_deref(), Some("normal"));
+    assert_eq!(final_update.end_reason.as_deref(), Some("stalemate"));
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_checkmate_credits_the_mating_side() {
+    let pgn_path = "test_checkmate.pgn";
+    let (mut config, _scripts) = mock_pair(pgn_path, "checkmate", "100", &[]);
+    // c7c8q mates
+    config.opening.fen = Some("k7/2P5/1K6/8/8/8/8/8 w - - 0 1".to_string());
+
+    let (final_update, moves) = play_one_game(config).await;
+    assert_eq!(moves, 1);
+    assert_eq!(final_update.result.as_deref(), Some("1-0"));
+    assert_eq!(final_update.termination.as_deref(), Some("normal"));
+    assert_eq!(final_update.end_reason.as_deref(), Some("checkmate"));
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_mock_crash_forfeits_after_legal_moves() {
+    let pgn_path = "test_mock_crash.pgn";
+    let (config, _scripts) = mock_pair(pgn_path, "crash", "100", &[("MOCK_ENGINE_MODE", "random"), ("MOCK_ENGINE_CRASH_AFTER", "2")]);
+
+    // Black's two random replies are legal, so the game only ends when it exits on its third go
+    let (final_update, moves) = play_one_game(config).await;
+    assert_eq!(final_update.result.as_deref(), Some("1-0"));
+    assert_eq!(final_update.termination.as_deref(), Some("abandoned"));
+    assert_eq!(moves, 5);
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_restarted_engine_keeps_the_crashed_search_on_its_clock() {
+    let pgn_path = "test_mock_restart_clock.pgn";
+    // Black thinks a second on every go and exits on each engine's second one
+    let (mut config, _scripts) = mock_pair(pgn_path, "restart_clock", "10", &[("MOCK_ENGINE_CRASH_AFTER", "1"), ("MOCK_ENGINE_THINK_MS", "1000")]);
+    config.time_control.base_ms = 10_000;
+    config.max_in_game_restarts = Some(1);
+
+    // B1, the crashed search, B2 on the restarted engine; the second crash forfeits
+    let (final_update, moves) = play_one_game(config).await;
+    assert_eq!(final_update.result.as_deref(), Some("1-0"));
+    assert_eq!(moves, 5);
+    assert!(final_update.black_time < 7_500, "the crashed second must be charged: {} ms left", final_update.black_time);
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_full_match_runs_to_completion() {
+    let pgn_path = "test_full_match.pgn".to_string();
+    let resume_path = std::env::temp_dir().join(format!("full_match_{}.json", std::process::id()));
+    // The second engine resigns on its first move, so the first engine wins both games
+    let (mut config, _scripts) = mock_pair(&pgn_path, "full_match", "100", &[("MOCK_ENGINE_MODE", "resign")]);
+    config.games_count = 2;
+    config.swap_sides = true;
+    config.overwrite_pgn = true;
+    config.resume_state_path = Some(resume_path.to_string_lossy().to_string());
+    config.adjudication.resign_score = Some(500);
+    config.adjudication.resign_move_count = Some(1);
+
+    let (channels, ArbiterReceivers { mut schedule_update_rx, mut tourney_stats_rx, .. }) = ArbiterChannels::new(100);
+
+    let final_states = tokio::spawn(async move {
+        let mut states = std::collections::BTreeMap::new();
+        while let Some(update) = schedule_update_rx.recv().await { states.insert(update.id, update.state); }
+        states
+    });
+    let last_stats = tokio::spawn(async move {
+        let mut last = None;
+        while let Some(stats) = tourney_stats_rx.recv().await { last = Some(stats); }
+        last
+    });
+
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    let complete = tokio::time::timeout(std::time::Duration::from_secs(60), arbiter.run_tournament()).await
+        .expect("match should finish on its own")
+        .expect("tournament failed");
+    let standings = arbiter.live_standings().await;
+    drop(arbiter);
+
+    assert_eq!(complete.reason, CompletionReason::AllGamesDone);
+    assert_eq!(complete.games_played, 2);
+    assert_eq!(complete.stats.total_games, 2);
+
+    let states = final_states.await.unwrap();
+    assert_eq!(states.len(), 2);
+    assert!(states.values().all(|state| state == "Finished"), "{:?}", states);
+
+    let stats = last_stats.await.unwrap().expect("no tournament stats");
+    assert_eq!(stats.total_games, 2);
+    assert_eq!((stats.wins, stats.losses, stats.draws), (2, 0, 0));
+
+    let names: Vec<&str> = standings.entries.iter().map(|entry| entry.engine_name.as_str()).collect();
+    assert_eq!(names, vec!["MockWhite", "MockBlack"]);
+    assert_eq!(standings.entries[0].points, 2.0);
+    assert_eq!(standings.entries[1].games_played, 2);
+
+    // The PGN writer runs on its own task
+    let mut pgn = String::new();
+    for _ in 0..50 {
+        pgn = std::fs::read_to_string(&pgn_path).unwrap_or_default();
+        if pgn.matches("[Event ").count() == 2 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(pgn.matches("[Event ").count(), 2, "{}", pgn);
+    // Persisted after every game, then removed once nothing is left to resume
+    assert!(!resume_path.exists(), "a finished tournament left its resume state behind");
+
+    let _ = std::fs::remove_file(&resume_path);
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[tokio::test]
+async fn test_abort_on_engine_disable_stops_tournament() {
+    let pgn_path = "test_abort_on_disable.pgn".to_string();
+    let mut config = mock_config(&pgn_path);
+    config.engines.truncate(2);
+    config.engines[1].id = Some("broken".into());
+    config.engines[1].path = "/nonexistent/engine".into();
+    config.games_count = 10;
+    config.abort_on_engine_disable = true;
+
+    let (channels, ArbiterReceivers { mut error_rx, mut schedule_update_rx, .. }) = ArbiterChannels::new(100);
+
+    let skipped = tokio::spawn(async move {
+        let mut skipped = 0;
+        while let Some(update) = schedule_update_rx.recv().await {
+            if update.state == "Skipped" { skipped += 1; }
+        }
+        skipped
+    });
+    let errors = tokio::spawn(async move {
+        let mut messages = Vec::new();
+        while let Some(error) = error_rx.recv().await { messages.push(error.message); }
+        messages
+    });
+
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    tokio::time::timeout(std::time::Duration::from_secs(30), arbiter.run_tournament()).await
+        .expect("tournament should stop once the engine is disabled")
+        .expect("tournament failed");
+    drop(arbiter);
+
+    assert_eq!(skipped.await.unwrap(), 0, "no game should be forfeited after the stop");
+    assert!(errors.await.unwrap().iter().any(|message| message.starts_with("Tournament stopped: MockBlack")));
+
+    if std::path::Path::new(&pgn_path).exists() {
+        let _ = std::fs::remove_file(pgn_path);
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_engine_that_never_finishes_the_handshake_is_disabled_at_the_limit() {
+    let pgn_path = "test_silent_engine_disabled.pgn".to_string();
+    let mut config = mock_config(&pgn_path);
+    config.engines.truncate(2);
+    config.engines[1].id = Some("silent".into());
+    let silent = mock_engine_with("silent", &[("MOCK_ENGINE_SILENT", "1")]);
+    config.engines[1].path = silent.path.clone();
+    config.engines[1].init_timeout_ms = Some(200);
+    config.games_count = 4;
+    config.spawn_failure_limit = Some(2);
+
+    let (channels, ArbiterReceivers { mut error_rx, .. }) = ArbiterChannels::new(100);
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    tokio::time::timeout(std::time::Duration::from_secs(30), arbiter.run_tournament()).await
+        .expect("tournament should finish once the engine is disabled")
+        .expect("tournament failed");
+
+    let mut errors = Vec::new();
+    while let Ok(error) = error_rx.try_recv() { errors.push(error); }
+    let counts: Vec<u32> = errors.iter().filter(|error| error.engine_id.as_deref() == Some("silent")).map(|error| error.failure_count).collect();
+    assert_eq!(counts, vec![1, 2], "the launch must not reset the count: {:?}", counts);
+    assert!(errors.iter().any(|error| error.disabled), "the engine should be disabled at the limit");
+
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_resume_plays_pending_games_of_a_later_repetition() {
+    let pgn_path = std::env::temp_dir().join(format!("resume_repetitions_{}.pgn", std::process::id())).to_string_lossy().to_string();
+    let mut config = mock_config(&pgn_path);
+    config.engines.truncate(2);
+    let _script = always_e2e4(&mut config, "resume_repetitions");
+    config.games_count = 2;
+    config.repetitions = Some(2);
+    config.resume_from_state = true;
+
+    // The first repetition and the first game of the second are done
+    let mut schedule = Arbiter::preview_schedule(&config).expect("preview failed");
+    assert_eq!(schedule.len(), 4);
+    for game in &mut schedule[..3] {
+        game.state = "Finished".to_string();
+        game.result = Some("1-0".to_string());
+    }
+
+    let (channels, ArbiterReceivers { mut schedule_update_rx, mut tourney_stats_rx, .. }) = ArbiterChannels::new(100);
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    arbiter.load_schedule_state(schedule).await;
+    tokio::time::timeout(std::time::Duration::from_secs(30), arbiter.run_tournament()).await
+        .expect("resumed tournament should finish")
+        .expect("tournament failed");
+
+    let mut states = std::collections::BTreeMap::new();
+    while let Ok(update) = schedule_update_rx.try_recv() {
+        states.insert(update.id, update.state);
+    }
+    assert_eq!(states.get(&4).map(String::as_str), Some("Finished"), "{:?}", states);
+    assert_eq!(states.keys().copied().max(), Some(4), "no third repetition should start: {:?}", states);
+    let mut last_stats = None;
+    while let Ok(stats) = tourney_stats_rx.try_recv() { last_stats = Some(stats); }
+    let summaries = last_stats.expect("no stats were sent").repetition_summaries;
+    assert_eq!(summaries.iter().map(|summary| summary.repetition).collect::<Vec<_>>(), vec![1, 2]);
+
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[tokio::test]
+async fn test_identify_engine_reads_id_lines() {
+    let info = mini_tcec_lib::uci::identify_engine(env!("CARGO_BIN_EXE_mock-engine")).await.expect("identify failed");
+    assert_eq!(info.name, "MockEngine 1.0");
+    assert_eq!(info.author, "Jules");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_chess960_needs_engines_that_declare_it() {
+    let script = mock_engine_with("chess960", &[("MOCK_ENGINE_OPTIONS", "UCI_Chess960,MultiPV")]);
+    let info = mini_tcec_lib::uci::identify_engine(&script.path).await.expect("identify failed");
+    assert!(info.supports_chess960 && info.supports_multipv);
+    assert!(!info.supports_ponder && !info.supports_limit_strength);
+
+    let mut config = mock_config("test_chess960_capabilities.pgn");
+    config.engines.truncate(2);
+    config.engines[0].path = script.path.clone();
+    assert!(Arbiter::validate_engine_capabilities(&config).await.is_ok(), "standard chess needs no capability");
+    config.variant = "chess960".to_string();
+    let err = Arbiter::validate_engine_capabilities(&config).await.unwrap_err();
+    assert!(err.to_string().contains("MockBlack does not declare UCI_Chess960"), "{}", err);
+    config.engines[1].path = script.path.clone();
+    assert!(Arbiter::validate_engine_capabilities(&config).await.is_ok());
+
+    // The check waits only as long as the engine's own init timeout
+    let silent = mock_engine_with("chess960_silent", &[("MOCK_ENGINE_SILENT", "1")]);
+    config.engines[1].path = silent.path.clone();
+    config.engines[1].init_timeout_ms = Some(200);
+    let started = std::time::Instant::now();
+    let err = Arbiter::validate_engine_capabilities(&config).await.unwrap_err();
+    assert!(err.to_string().contains("never sent uciok (waited 200 ms)"), "{}", err);
+    assert!(started.elapsed() < std::time::Duration::from_secs(2));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_handshake_ignores_startup_banner() {
+    let script = mock_engine_with("banner", &[("MOCK_ENGINE_BANNER", "1")]);
+    let info = mini_tcec_lib::uci::identify_engine(&script.path).await.expect("identify failed");
+    assert_eq!(info.name, "MockEngine 1.0");
+    let options: Vec<&str> = info.options.iter().map(|opt| opt.name.as_str()).collect();
+    assert_eq!(options, vec!["Hash", "Threads"]);
+
+    // A game still gets through the handshake and plays
+    let pgn_path = "test_output_banner.pgn";
+    let (mut config, _scripts) = mock_pair(pgn_path, "banner_game", "10", &[("MOCK_ENGINE_BANNER", "1")]);
+    config.engines[0].path = script.path.clone();
+    config.opening.fen = Some("k7/2P5/1K6/8/8/8/8/8 w - - 0 1".to_string());
+    let (final_update, moves) = play_one_game(config).await;
+    assert_eq!(final_update.result.as_deref(), Some("1-0"));
+    assert_eq!(moves, 1);
+    let _ = std::fs::remove_file(pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_verify_engine_catches_illegal_self_play_moves() {
+    let report = mini_tcec_lib::verify::verify_engine(&mock_engine_with("verify", &[("MOCK_ENGINE_MODE", "e2e4")]).path).await.expect("verify failed");
+    assert!(report.handshake_ok);
+    // e2e4 again as Black
+    assert_eq!(report.moves_played, 1);
+    assert!(!report.moves_legal);
+    assert_eq!(report.average_nps, None);
+    assert!(report.warnings.iter().any(|w| w.contains("e2e4 is not a legal move")), "{:?}", report.warnings);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_benchmark_engine_reports_depth_and_nodes_per_position() {
+    let script = mock_engine_with("bench", &[("MOCK_ENGINE_THINK_MS", "100")]);
+    let positions = vec![
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1".to_string(),
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1".to_string(),
+    ];
+    let result = mini_tcec_lib::bench::benchmark_engine(&script.path, Some(positions), 300).await.expect("benchmark failed");
+    assert_eq!(result.positions.len(), 2);
+    assert!(result.positions.iter().all(|position| position.depth == 2 && position.nodes == 200 && position.bestmove.is_some()));
+    assert_eq!(result.total_nodes, 400);
+    // The mock engine never reports nps
+    assert_eq!(result.average_nps, None);
+
+    let err = mini_tcec_lib::bench::benchmark_engine(&script.path, Some(vec!["not a fen".to_string()]), 300).await.unwrap_err();
+    assert!(err.to_string().contains("Invalid benchmark FEN"), "{}", err);
+    let err = mini_tcec_lib::bench::benchmark_engine(&script.path, None, 0).await.unwrap_err();
+    assert!(err.to_string().contains("at least 1 ms"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_small_output_buffer_reports_lagged_lines() {
+    let engine = mini_tcec_lib::uci::AsyncEngine::spawn_with_buffer(env!("CARGO_BIN_EXE_mock-engine"), None, 1).await.expect("spawn failed");
+    let mut rx = engine.stdout_broadcast.subscribe();
+    engine.send("uci".to_string()).await.unwrap();
+    // Let all three handshake lines arrive before reading any
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    match rx.recv().await {
+        Err(tokio::sync::broadcast::error::RecvError::Lagged(count)) => engine.record_lag(count),
+        other => panic!("expected a lag, got {:?}", other),
+    }
+    assert_eq!(rx.recv().await.unwrap(), "uciok");
+    assert_eq!(engine.lagged_lines(), 2);
+    let _ = engine.kill().await;
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_time_limit_stops_scheduling_and_finishes_started_games() {
+    let pgn_path = "test_time_limit.pgn".to_string();
+    let (mut config, _scripts) = mock_pair(&pgn_path, "time_limit", "100", &[("MOCK_ENGINE_MODE", "resign")]);
+    config.games_count = 50;
+    config.max_duration_secs = Some(1);
+    config.adjudication.resign_score = Some(500);
+    config.adjudication.resign_move_count = Some(1);
+
+    let (channels, ArbiterReceivers { mut schedule_update_rx, .. }) = ArbiterChannels::new(100);
+
+    let final_states = tokio::spawn(async move {
+        let mut states = std::collections::BTreeMap::new();
+        while let Some(update) = schedule_update_rx.recv().await { states.insert(update.id, update.state); }
+        states
+    });
+
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    let complete = tokio::time::timeout(std::time::Duration::from_secs(30), arbiter.run_tournament()).await
+        .expect("tournament should stop on its own once the time limit passes")
+        .expect("tournament failed");
+    drop(arbiter);
+
+    assert_eq!(complete.reason, CompletionReason::TimeLimitReached);
+    assert!(complete.games_played >= 1 && complete.games_played < 50, "{} games played", complete.games_played);
+    // Nothing is left half-played: every game either finished or was dropped unstarted
+    let states = final_states.await.unwrap();
+    assert_eq!(states.values().filter(|state| *state == "Finished").count(), complete.games_played as usize);
+    assert!(states.values().all(|state| state == "Finished" || state == "Removed"), "{:?}", states);
+
+    let _ = std::fs::remove_file(&pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_spawn_stagger_spaces_out_the_first_games() {
+    let pgn_path = "test_spawn_stagger.pgn".to_string();
+    let (mut config, _scripts) = mock_pair(&pgn_path, "stagger", "100", &[("MOCK_ENGINE_MODE", "resign")]);
+    config.games_count = 2;
+    config.swap_sides = true;
+    config.concurrency = Some(2);
+    config.spawn_stagger_ms = Some(600);
+    config.adjudication.resign_score = Some(500);
+    config.adjudication.resign_move_count = Some(1);
+
+    let (channels, ArbiterReceivers { game_update_rx: mut game_rx, .. }) = ArbiterChannels::new(100);
+
+    // When each game sent its first update
+    let first_updates = tokio::spawn(async move {
+        let mut first = std::collections::BTreeMap::new();
+        while let Some(update) = game_rx.recv().await {
+            first.entry(update.game_id).or_insert_with(std::time::Instant::now);
+        }
+        first
+    });
+
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    let complete = tokio::time::timeout(std::time::Duration::from_secs(30), arbiter.run_tournament()).await
+        .expect("match should finish on its own")
+        .expect("tournament failed");
+    drop(arbiter);
+    assert_eq!(complete.games_played, 2);
+
+    let first = first_updates.await.unwrap();
+    let starts: Vec<std::time::Instant> = first.values().copied().collect();
+    assert_eq!(starts.len(), 2);
+    assert!(starts[1].duration_since(starts[0]) >= std::time::Duration::from_millis(500), "{:?}", starts[1].duration_since(starts[0]));
+
+    let _ = std::fs::remove_file(&pgn_path);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_games_count_zero_plays_until_sprt_resolves() {
+    let pgn_path = "test_until_sprt.pgn".to_string();
+    let mut config = mock_config(&pgn_path);
+    // Without side swaps the first engine is always White: a forced run of wins.
+    config.engines.truncate(2);
+    let _script = always_e2e4(&mut config, "until_sprt");
+    config.swap_sides = false;
+    config.games_count = 0;
+    config.concurrency = Some(2);
+    config.sprt_enabled = true;
+    config.sprt_config = Some(mini_tcec_lib::sprt::SprtConfig { h0_elo: 0.0, h1_elo: 400.0, draw_ratio: 0.0, alpha: 0.2, beta: 0.2 });
+
+    let (channels, ArbiterReceivers { mut tourney_stats_rx, .. }) = ArbiterChannels::new(100);
+
+    let last_stats = tokio::spawn(async move {
+        let mut last = None;
+        while let Some(stats) = tourney_stats_rx.recv().await { last = Some(stats); }
+        last
+    });
+
+    let arbiter = Arbiter::new(config, channels).await.expect("Failed to create arbiter");
+    let complete = tokio::time::timeout(std::time::Duration::from_secs(60), arbiter.run_tournament()).await
+        .expect("tournament should stop on its own once the SPRT resolves")
+        .expect("tournament failed");
+    drop(arbiter);
+    assert_eq!(complete.reason, CompletionReason::SprtResolved);
+
+    let stats = last_stats.await.unwrap().expect("no final tournament stats");
+    assert_eq!(stats.sprt_state, "Accept");
+    assert_eq!(stats.losses, 0);
+    assert!(stats.wins >= 3);
+
+    if std::path::Path::new(&pgn_path).exists() {
+        let _ = std::fs::remove_file(pgn_path);
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_epd_suite_scores_bestmoves() {
+    let path = std::env::temp_dir().join(format!("suite_{}.epd", std::process::id()));
+    std::fs::write(&path, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - bm e4; id \"king pawn\";\n\
+                           rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - bm d4; id \"queen pawn\";\n").unwrap();
+    let mut engine = mock_config("unused.pgn").engines.remove(0);
+    let script = mock_engine_with("epd", &[("MOCK_ENGINE_MODE", "e2e4")]);
+    engine.path = script.path.clone();
+
+    let report = mini_tcec_lib::epd::run_epd_suite(&engine, &path.to_string_lossy(), 300).await.expect("suite failed");
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!((report.solved, report.total), (1, 2));
+    assert!(report.results[0].solved);
+    assert_eq!(report.results[1].bestmove.as_deref(), Some("e2e4"));
+}